@@ -0,0 +1,150 @@
+use crate::config::{skip_err, Config};
+use crate::err::RpErr;
+use std::fmt::{Display, Formatter};
+use std::process::{Child, Command, Stdio};
+
+const PLACEHOLDER: &str = "{v}";
+
+/// 外部命令参数
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct CmdArg {
+    /// 程序名称或路径，名称从环境变量`PATH`中查找
+    pub(crate) program: String,
+    /// 程序参数
+    pub(crate) args: Vec<String>,
+}
+
+impl CmdArg {
+    /// 第一个值为程序，其余值为程序参数，至少需要一个值。
+    pub(crate) fn new(mut values: Vec<String>) -> CmdArg {
+        let program = values.remove(0);
+        CmdArg { program, args: values }
+    }
+
+    /// 启动命令，捕获标准输出，标准错误直接输出到当前进程的标准错误，标准输入为空。
+    pub(crate) fn spawn_reader(&self) -> Result<Child, RpErr> {
+        self.spawn(Stdio::null(), Stdio::piped())
+    }
+
+    /// 启动命令，捕获标准输入，标准输出和标准错误直接输出到当前进程。
+    pub(crate) fn spawn_writer(&self) -> Result<Child, RpErr> {
+        self.spawn(Stdio::piped(), Stdio::inherit())
+    }
+
+    fn spawn(&self, stdin: Stdio, stdout: Stdio) -> Result<Child, RpErr> {
+        Command::new(&self.program)
+            .args(&self.args)
+            .stdin(stdin)
+            .stdout(stdout)
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|err| RpErr::RunCmdErr { cmd: self.to_string(), err: err.to_string() })
+    }
+
+    /// 使用数据构建命令，参数中的{v}替换为数据，包含{v}的参数按照每个数据重复展开；
+    /// 如果所有参数都不包含{v}，则将数据追加为最后的参数。
+    pub(crate) fn command_with(&self, items: &[String]) -> Command {
+        let mut command = Command::new(&self.program);
+        if self.args.iter().any(|arg| arg.contains(PLACEHOLDER)) {
+            for arg in &self.args {
+                if arg.contains(PLACEHOLDER) {
+                    command.args(items.iter().map(|item| arg.replace(PLACEHOLDER, item)));
+                } else {
+                    command.arg(arg);
+                }
+            }
+        } else {
+            command.args(&self.args).args(items);
+        }
+        command
+    }
+
+    /// 等待命令结束并检查退出状态，如果全局配置了跳过错误，则忽略失败的退出状态。
+    pub(crate) fn wait(&self, child: &mut Child, configs: &[Config]) -> Result<(), RpErr> {
+        let status = child.wait().map_err(|err| RpErr::RunCmdErr { cmd: self.to_string(), err: err.to_string() })?;
+        if status.success() || skip_err(configs) {
+            Ok(())
+        } else {
+            Err(RpErr::CmdExitErr { cmd: self.to_string(), status: status.to_string() })
+        }
+    }
+
+    /// 等待输出命令结束，退出状态失败时使用命令的退出码作为错误的退出码。
+    pub(crate) fn wait_output(&self, child: &mut Child) -> Result<(), RpErr> {
+        let status = child.wait().map_err(|err| RpErr::RunCmdErr { cmd: self.to_string(), err: err.to_string() })?;
+        match status.code() {
+            _ if status.success() => Ok(()),
+            Some(code) => Err(RpErr::OutputCmdExitErr { cmd: self.to_string(), code }),
+            None => Err(RpErr::CmdExitErr { cmd: self.to_string(), status: status.to_string() }), // 被信号终止
+        }
+    }
+}
+
+impl Display for CmdArg {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.program)?;
+        for arg in &self.args {
+            write!(f, " {arg}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_new() {
+        let cmd = CmdArg::new(vec!["git".to_string(), "log".to_string(), "--oneline".to_string()]);
+        assert_eq!(cmd.program, "git");
+        assert_eq!(cmd.args, vec!["log", "--oneline"]);
+        assert_eq!(cmd.to_string(), "git log --oneline");
+    }
+
+    #[test]
+    fn test_command_with() {
+        let items = vec!["a".to_string(), "b".to_string()];
+        let args = |cmd: &[&str]| {
+            let cmd = CmdArg::new(cmd.iter().map(|s| s.to_string()).collect());
+            cmd.command_with(&items).get_args().map(|s| s.to_string_lossy().into_owned()).collect::<Vec<_>>()
+        };
+        assert_eq!(args(&["echo"]), vec!["a", "b"]);
+        assert_eq!(args(&["echo", "-n"]), vec!["-n", "a", "b"]);
+        assert_eq!(args(&["echo", "-e{v}", "--", "{v}.txt"]), vec!["-ea", "-eb", "--", "a.txt", "b.txt"]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_spawn_reader() {
+        let cmd = CmdArg::new(vec!["sh".to_string(), "-c".to_string(), "echo out; echo err >&2".to_string()]);
+        let mut child = cmd.spawn_reader().unwrap();
+        let mut out = String::new();
+        child.stdout.take().unwrap().read_to_string(&mut out).unwrap();
+        assert_eq!(out, "out\n");
+        assert_eq!(cmd.wait(&mut child, &[]), Ok(()));
+
+        let cmd = CmdArg::new(vec!["sh".to_string(), "-c".to_string(), "exit 3".to_string()]);
+        let mut child = cmd.spawn_reader().unwrap();
+        assert!(matches!(cmd.wait(&mut child, &[]), Err(RpErr::CmdExitErr { .. })));
+        let mut child = cmd.spawn_reader().unwrap();
+        assert_eq!(cmd.wait(&mut child, &[Config::SkipErr]), Ok(()));
+
+        let cmd = CmdArg::new(vec!["rp_not_exists_program".to_string()]);
+        assert!(matches!(cmd.spawn_reader(), Err(RpErr::RunCmdErr { .. })));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_spawn_writer() {
+        use std::io::Write;
+        let cmd = CmdArg::new(vec!["sh".to_string(), "-c".to_string(), "read line; exit $line".to_string()]);
+        let mut child = cmd.spawn_writer().unwrap();
+        child.stdin.take().unwrap().write_all(b"0\n").unwrap();
+        assert_eq!(cmd.wait_output(&mut child), Ok(()));
+        let mut child = cmd.spawn_writer().unwrap();
+        child.stdin.take().unwrap().write_all(b"7\n").unwrap();
+        assert_eq!(cmd.wait_output(&mut child), Err(RpErr::OutputCmdExitErr { cmd: cmd.to_string(), code: 7 }));
+    }
+}
@@ -0,0 +1,252 @@
+use std::io;
+use std::io::{BufRead, BufReader, Read, Write};
+
+/// 文件的压缩格式
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum Compress {
+    /// 不压缩
+    Plain,
+    Gzip,
+    Zstd,
+    Bzip2,
+    Xz,
+}
+
+impl Compress {
+    /// 根据名称获取压缩格式，忽略大小写。
+    pub(crate) fn from_name(name: &str) -> Option<Compress> {
+        match name.to_ascii_lowercase().as_str() {
+            "plain" => Some(Compress::Plain),
+            "gzip" | "gz" => Some(Compress::Gzip),
+            "zstd" | "zst" => Some(Compress::Zstd),
+            "bzip2" | "bz2" => Some(Compress::Bzip2),
+            "xz" => Some(Compress::Xz),
+            _ => None,
+        }
+    }
+
+    /// 根据文件扩展名推断压缩格式，无法推断时视为不压缩。
+    pub(crate) fn infer(file: &str) -> Compress {
+        let lower_file = file.to_ascii_lowercase();
+        if lower_file.ends_with(".gz") {
+            Compress::Gzip
+        } else if lower_file.ends_with(".zst") || lower_file.ends_with(".zstd") {
+            Compress::Zstd
+        } else if lower_file.ends_with(".bz2") {
+            Compress::Bzip2
+        } else if lower_file.ends_with(".xz") {
+            Compress::Xz
+        } else {
+            Compress::Plain
+        }
+    }
+
+    /// 压缩级别是否有效。
+    pub(crate) fn is_valid_level(&self, level: u32) -> bool {
+        match self {
+            Compress::Plain => false,
+            Compress::Gzip | Compress::Xz => level <= 9,
+            Compress::Zstd => (1..=22).contains(&level),
+            Compress::Bzip2 => (1..=9).contains(&level),
+        }
+    }
+
+    /// 根据数据开头的魔数识别压缩格式，无法识别时视为不压缩。
+    fn detect(head: &[u8]) -> Compress {
+        if head.starts_with(&[0x1f, 0x8b]) {
+            Compress::Gzip
+        } else if head.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Compress::Zstd
+        } else if head.starts_with(b"BZh") {
+            Compress::Bzip2
+        } else if head.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]) {
+            Compress::Xz
+        } else {
+            Compress::Plain
+        }
+    }
+
+    /// 根据文件开头的魔数识别文件的压缩格式，无法识别时视为不压缩。
+    pub(crate) fn detect_file(file: &str) -> io::Result<Compress> {
+        let mut head = Vec::new();
+        std::fs::File::open(file)?.take(6).read_to_end(&mut head)?;
+        Ok(Self::detect(&head))
+    }
+
+    /// 包装解压读取器，未指定压缩格式时根据魔数识别。
+    pub(crate) fn decoder(compress: Option<Compress>, reader: impl Read + 'static) -> io::Result<Box<dyn Read>> {
+        let mut reader = BufReader::new(reader);
+        let compress = match compress {
+            Some(compress) => compress,
+            None => Self::detect(reader.fill_buf()?),
+        };
+        Ok(match compress {
+            Compress::Plain => Box::new(reader),
+            Compress::Gzip => Box::new(flate2::bufread::MultiGzDecoder::new(reader)),
+            Compress::Zstd => Box::new(zstd::Decoder::with_buffer(reader)?),
+            Compress::Bzip2 => Box::new(bzip2::bufread::MultiBzDecoder::new(reader)),
+            Compress::Xz => Box::new(xz2::bufread::XzDecoder::new_multi_decoder(reader)),
+        })
+    }
+
+    /// 包装压缩写入器，未指定压缩格式时根据文件扩展名推断，未指定压缩级别时使用默认级别。
+    pub(crate) fn encoder<W: Write>(
+        compress: Option<Compress>, level: Option<u32>, file: &str, writer: W,
+    ) -> io::Result<CompressWriter<W>> {
+        Ok(match compress.unwrap_or_else(|| Self::infer(file)) {
+            Compress::Plain => CompressWriter::Plain(writer),
+            Compress::Gzip => CompressWriter::Gzip(flate2::write::GzEncoder::new(
+                writer,
+                level.map(flate2::Compression::new).unwrap_or_default(),
+            )),
+            Compress::Zstd => CompressWriter::Zstd(zstd::Encoder::new(writer, level.unwrap_or(0) as i32)?),
+            Compress::Bzip2 => CompressWriter::Bzip2(bzip2::write::BzEncoder::new(
+                writer,
+                level.map(bzip2::Compression::new).unwrap_or_default(),
+            )),
+            Compress::Xz => CompressWriter::Xz(xz2::write::XzEncoder::new(writer, level.unwrap_or(6))),
+        })
+    }
+}
+
+/// 压缩写入器，每次打开（包括追加打开）都会产生独立的压缩流（例如gzip成员、zstd帧），
+/// 解压时会依次解压所有压缩流，因此追加写入的文件依然可以被完整解压。
+/// 丢弃时会自动完成压缩，但是会忽略错误，需要处理错误时应该显式调用`finish`。
+pub(crate) enum CompressWriter<W: Write> {
+    Plain(W),
+    Gzip(flate2::write::GzEncoder<W>),
+    Zstd(zstd::Encoder<'static, W>),
+    Bzip2(bzip2::write::BzEncoder<W>),
+    Xz(xz2::write::XzEncoder<W>),
+}
+
+impl<W: Write> CompressWriter<W> {
+    /// 完成压缩，写入压缩流的结尾数据，可以重复调用。
+    pub(crate) fn finish(&mut self) -> io::Result<()> {
+        match self {
+            CompressWriter::Plain(writer) => writer.flush(),
+            CompressWriter::Gzip(writer) => writer.try_finish(),
+            CompressWriter::Zstd(writer) => writer.do_finish(),
+            CompressWriter::Bzip2(writer) => writer.try_finish(),
+            CompressWriter::Xz(writer) => writer.try_finish(),
+        }
+    }
+
+    /// 获取内部的写入器，写入内部的写入器会破坏压缩流，通常仅在完成压缩后使用。
+    pub(crate) fn get_mut(&mut self) -> &mut W {
+        match self {
+            CompressWriter::Plain(writer) => writer,
+            CompressWriter::Gzip(writer) => writer.get_mut(),
+            CompressWriter::Zstd(writer) => writer.get_mut(),
+            CompressWriter::Bzip2(writer) => writer.get_mut(),
+            CompressWriter::Xz(writer) => writer.get_mut(),
+        }
+    }
+}
+
+impl<W: Write> Write for CompressWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            CompressWriter::Plain(writer) => writer.write(buf),
+            CompressWriter::Gzip(writer) => writer.write(buf),
+            CompressWriter::Zstd(writer) => writer.write(buf),
+            CompressWriter::Bzip2(writer) => writer.write(buf),
+            CompressWriter::Xz(writer) => writer.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            CompressWriter::Plain(writer) => writer.flush(),
+            CompressWriter::Gzip(writer) => writer.flush(),
+            CompressWriter::Zstd(writer) => writer.flush(),
+            CompressWriter::Bzip2(writer) => writer.flush(),
+            CompressWriter::Xz(writer) => writer.flush(),
+        }
+    }
+}
+
+impl<W: Write> Drop for CompressWriter<W> {
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+
+    fn decode(compress: Option<Compress>, data: Vec<u8>) -> Vec<u8> {
+        let mut res = Vec::new();
+        Compress::decoder(compress, Cursor::new(data)).unwrap().read_to_end(&mut res).unwrap();
+        res
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(Compress::from_name("GZIP"), Some(Compress::Gzip));
+        assert_eq!(Compress::from_name("zstd"), Some(Compress::Zstd));
+        assert_eq!(Compress::from_name("bzip2"), Some(Compress::Bzip2));
+        assert_eq!(Compress::from_name("xz"), Some(Compress::Xz));
+        assert_eq!(Compress::from_name("plain"), Some(Compress::Plain));
+        assert_eq!(Compress::from_name("zip"), None);
+    }
+
+    #[test]
+    fn test_decoder() {
+        let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gzip.write_all(b"a\nb\n").unwrap();
+        let mut gzip = gzip.finish().unwrap();
+        gzip.extend(gzip.clone()); // 多个gzip成员
+        assert_eq!(decode(None, gzip.clone()), b"a\nb\na\nb\n");
+        assert_eq!(decode(Some(Compress::Gzip), gzip), b"a\nb\na\nb\n");
+        assert_eq!(decode(None, zstd::encode_all(&b"zstd"[..], 0).unwrap()), b"zstd");
+        let mut bzip2 = bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::default());
+        bzip2.write_all(b"bzip2").unwrap();
+        assert_eq!(decode(None, bzip2.finish().unwrap()), b"bzip2");
+        let mut xz = xz2::write::XzEncoder::new(Vec::new(), 6);
+        xz.write_all(b"xz").unwrap();
+        assert_eq!(decode(None, xz.finish().unwrap()), b"xz");
+        assert_eq!(decode(None, b"plain".to_vec()), b"plain");
+        assert_eq!(decode(Some(Compress::Plain), vec![0x1f, 0x8b]), [0x1f, 0x8b]);
+        assert_eq!(decode(None, Vec::new()), b"");
+    }
+
+    #[test]
+    fn test_infer() {
+        assert_eq!(Compress::infer("out.txt.gz"), Compress::Gzip);
+        assert_eq!(Compress::infer("OUT.ZST"), Compress::Zstd);
+        assert_eq!(Compress::infer("out.bz2"), Compress::Bzip2);
+        assert_eq!(Compress::infer("out.xz"), Compress::Xz);
+        assert_eq!(Compress::infer("out.txt"), Compress::Plain);
+    }
+
+    #[test]
+    fn test_encoder() {
+        for compress in [Compress::Plain, Compress::Gzip, Compress::Zstd, Compress::Bzip2, Compress::Xz] {
+            let mut data = Vec::new();
+            for item in ["a\n", "b\n"] {
+                // 模拟多次追加打开，每次打开产生独立的压缩流
+                let mut writer = Compress::encoder(Some(compress), None, "out", &mut data).unwrap();
+                writer.write_all(item.as_bytes()).unwrap();
+                writer.finish().unwrap();
+            }
+            assert_eq!(decode(None, data), b"a\nb\n", "{compress:?}");
+        }
+        let mut data = Vec::new();
+        let mut writer = Compress::encoder(None, Some(1), "out.gz", &mut data).unwrap();
+        writer.write_all(b"a").unwrap();
+        drop(writer); // 丢弃时自动完成压缩
+        assert_eq!(decode(Some(Compress::Gzip), data), b"a");
+    }
+
+    #[test]
+    fn test_is_valid_level() {
+        assert!(Compress::Gzip.is_valid_level(0));
+        assert!(!Compress::Gzip.is_valid_level(10));
+        assert!(!Compress::Zstd.is_valid_level(0));
+        assert!(Compress::Zstd.is_valid_level(22));
+        assert!(!Compress::Plain.is_valid_level(1));
+    }
+}
@@ -1,14 +1,38 @@
+use crate::config::{is_nocase, Config};
 use crate::err::RpErr;
+use crate::json::JsonPath;
 use crate::{Float, Integer, Num};
 use cmd_help::CmdHelp;
-use regex::Regex;
+use regex::{Regex, RegexBuilder};
+use std::borrow::Cow;
 use std::fmt::Debug;
 
 /// 条件
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, CmdHelp)]
 pub(crate) enum Condition {
+    /// <select>
+    ///     按照选择条件选择数据，支持的选择条件见下文。
     Yes(Select),
-    Not(Select),
+    /// not <condition>
+    ///     否定条件，选择不满足条件的数据，优先级最高。
+    ///     例如：
+    ///         not upper
+    ///         not ( empty or blank )
+    Not(Box<Condition>),
+    /// <condition> and <condition>[ and <condition>][...]
+    ///     选择同时满足所有条件的数据，优先级低于not，高于or。
+    ///     例如：
+    ///         num and not num 0
+    ///         len 3, and lower
+    And(Vec<Condition>),
+    /// <condition> or <condition>[ or <condition>][...]
+    ///     选择满足任一条件的数据，优先级最低。
+    ///     可以使用`(`和`)`包裹条件以调整优先级，`(`和`)`需要作为独立的参数。
+    ///     例如：
+    ///         upper or lower
+    ///         empty or num and len 3,
+    ///         ( empty or num ) and len 3,
+    Or(Vec<Condition>),
 }
 
 impl Condition {
@@ -16,12 +40,66 @@ impl Condition {
         if not { select.not() } else { select.yes() }
     }
 
-    pub(crate) fn test(&self, input: &str) -> bool {
-        match self {
-            Condition::Yes(select) => select.select(input),
-            Condition::Not(select) => !select.select(input),
-        }
+    /// 合并多个条件为与条件，只有一个条件时直接返回此条件
+    pub(crate) fn new_and(mut conds: Vec<Condition>) -> Condition {
+        if conds.len() == 1 { conds.pop().unwrap() } else { Condition::And(conds) }
+    }
+
+    /// 合并多个条件为或条件，只有一个条件时直接返回此条件
+    pub(crate) fn new_or(mut conds: Vec<Condition>) -> Condition {
+        if conds.len() == 1 { conds.pop().unwrap() } else { Condition::Or(conds) }
+    }
+
+    #[inline]
+    pub(crate) fn not(self) -> Condition {
+        Condition::Not(Box::new(self))
+    }
+
+    /// 根据全局配置调整条件中各个选择的大小写敏感性
+    pub(crate) fn with_configs(self, configs: &[Config]) -> Result<Condition, RpErr> {
+        Ok(match self {
+            Condition::Yes(select) => Condition::Yes(select.with_configs(configs)?),
+            Condition::Not(cond) => cond.with_configs(configs)?.not(),
+            Condition::And(conds) => {
+                Condition::And(conds.into_iter().map(|cond| cond.with_configs(configs)).collect::<Result<_, _>>()?)
+            }
+            Condition::Or(conds) => {
+                Condition::Or(conds.into_iter().map(|cond| cond.with_configs(configs)).collect::<Result<_, _>>()?)
+            }
+        })
     }
+
+    /// 测试数据是否满足条件，无法测试时（例如数据不是有效的JSON）返回错误，错误不受`not`影响。
+    pub(crate) fn test(&self, input: &str) -> Result<bool, RpErr> {
+        Ok(match self {
+            Condition::Yes(select) => select.select(input)?,
+            Condition::Not(cond) => !cond.test(input)?,
+            Condition::And(conds) => {
+                for cond in conds {
+                    if !cond.test(input)? {
+                        return Ok(false);
+                    }
+                }
+                true
+            }
+            Condition::Or(conds) => {
+                for cond in conds {
+                    if cond.test(input)? {
+                        return Ok(true);
+                    }
+                }
+                false
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum LiteralSelectMode {
+    Contains,
+    Prefix,
+    Suffix,
+    Eq,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -99,6 +177,42 @@ pub(crate) enum Select {
     ///     例如：
     ///         reg '\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3}'
     RegMatch { regex: Regex },
+    /// [not] contains <text>[ nocase]
+    ///     选择包含给定字符串的数据。
+    /// [not] prefix <text>[ nocase]
+    ///     选择以给定字符串开头的数据。
+    /// [not] suffix <text>[ nocase]
+    ///     选择以给定字符串结尾的数据。
+    /// [not] eq <text>[ nocase]
+    ///     选择等于给定字符串的数据。
+    ///     <text>  字符串，必选。
+    ///     nocase  忽略大小写，可选，未指定时使用全局配置。
+    ///     例如：
+    ///         contains abc
+    ///         prefix http:// nocase
+    ///         not suffix .txt
+    ///         eq ''
+    Literal { mode: LiteralSelectMode, text: String /*nocase时需要转为小写*/, nocase: bool },
+    /// [not] find <exp>[ nocase]
+    ///     选择包含给定正则表达式匹配内容的数据，与reg不同，无需匹配整个数据。
+    ///     <exp>   正则表达式，必选。
+    ///     nocase  忽略大小写，可选，未指定时使用全局配置。
+    ///     例如：
+    ///         find '\d+'
+    ///         find 'error|warn' nocase
+    RegFind { regex: Regex, nocase: bool },
+    /// [not] json <path> <select>
+    ///     将数据解析为JSON，按照路径提取值，并使用选择条件选择提取的值。
+    ///     <path>      JSON路径，以`.`开头，例如`.a.b[0]`，`.`表示整个值，必选。
+    ///                 字符串值使用原始文本，其他值使用紧凑的JSON文本。
+    ///     <select>    应用于提取的值的选择条件，支持可选否定，必选。
+    ///     路径不存在时不选择；数据不是有效的JSON时终止，如果全局配置了跳过错误，
+    ///     则无论是否否定，`:take`和`:drop`都丢弃此数据。
+    ///     例如：
+    ///         json .status num 200,299
+    ///         json .user.name not empty
+    ///         json .tags[0] eq prod nocase
+    Json { path: JsonPath, cond: Box<Condition> },
 }
 
 impl PartialEq for Select {
@@ -116,6 +230,16 @@ impl PartialEq for Select {
             (Select::Text { mode: l }, Select::Text { mode: r }) => l == r,
             // Regex 比较模式字符串
             (Select::RegMatch { regex: l }, Select::RegMatch { regex: r }) => l.as_str() == r.as_str(),
+            (
+                Select::Literal { mode: l_mode, text: l_text, nocase: l_nocase },
+                Select::Literal { mode: r_mode, text: r_text, nocase: r_nocase },
+            ) => l_mode == r_mode && l_text == r_text && l_nocase == r_nocase,
+            (Select::RegFind { regex: l, nocase: l_nocase }, Select::RegFind { regex: r, nocase: r_nocase }) => {
+                l.as_str() == r.as_str() && l_nocase == r_nocase
+            }
+            (Select::Json { path: l_path, cond: l_cond }, Select::Json { path: r_path, cond: r_cond }) => {
+                l_path == r_path && l_cond == r_cond
+            }
             // 其他情况都不相等
             _ => false,
         }
@@ -135,6 +259,34 @@ impl Select {
             .map(|regex| Select::RegMatch { regex })
             .map_err(|err| RpErr::ParseRegexErr { reg, err: err.to_string() })
     }
+    pub(crate) fn new_literal(mode: LiteralSelectMode, text: String, nocase: bool) -> Select {
+        Select::Literal { mode, text: if nocase { text.to_lowercase() } else { text }, nocase }
+    }
+    pub(crate) fn new_reg_find(regex: &str, nocase: bool) -> Result<Select, RpErr> {
+        RegexBuilder::new(regex)
+            .case_insensitive(nocase)
+            .build()
+            .map(|regex| Select::RegFind { regex, nocase })
+            .map_err(|err| RpErr::ParseRegexErr { reg: regex.to_owned(), err: err.to_string() })
+    }
+    /// `cond`为应用于提取的值的单个选择，可以是否定的选择。
+    pub(crate) fn new_json(path: JsonPath, cond: Condition) -> Select {
+        Select::Json { path, cond: Box::new(cond) }
+    }
+
+    /// 全局忽略大小写而自身未指定`nocase`时，转换为忽略大小写的选择
+    fn with_configs(self, configs: &[Config]) -> Result<Select, RpErr> {
+        match self {
+            Select::Literal { mode, text, nocase } if !nocase && is_nocase(nocase, configs) => {
+                Ok(Select::new_literal(mode, text, true))
+            }
+            Select::RegFind { regex, nocase } if !nocase && is_nocase(nocase, configs) => {
+                Select::new_reg_find(regex.as_str(), true)
+            }
+            Select::Json { path, cond } => Ok(Select::Json { path, cond: Box::new(cond.with_configs(configs)?) }),
+            select => Ok(select),
+        }
+    }
 
     #[inline]
     pub(crate) fn yes(self) -> Condition {
@@ -143,11 +295,11 @@ impl Select {
 
     #[inline]
     pub(crate) fn not(self) -> Condition {
-        Condition::Not(self)
+        self.yes().not()
     }
 
-    fn select(&self, input: &str) -> bool {
-        match self {
+    fn select(&self, input: &str) -> Result<bool, RpErr> {
+        Ok(match self {
             Select::TextLenRange { min, max } => {
                 let len = *&input.chars().count();
                 min.map_or(true, |min_len| len >= min_len) && max.map_or(true, |max_len| len <= max_len)
@@ -177,7 +329,21 @@ impl Select {
                 TextSelectMode::Blank => input.chars().all(|c| c.is_whitespace()),
             },
             Select::RegMatch { regex } => regex.is_match(input),
-        }
+            Select::Literal { mode, text, nocase } => {
+                let input = if *nocase { Cow::Owned(input.to_lowercase()) } else { Cow::Borrowed(input) };
+                match mode {
+                    LiteralSelectMode::Contains => input.contains(text.as_str()),
+                    LiteralSelectMode::Prefix => input.starts_with(text.as_str()),
+                    LiteralSelectMode::Suffix => input.ends_with(text.as_str()),
+                    LiteralSelectMode::Eq => input == text.as_str(),
+                }
+            }
+            Select::RegFind { regex, .. } => regex.is_match(input),
+            Select::Json { path, cond } => match path.extract(input)? {
+                Some(value) => cond.test(&value)?,
+                None => false,
+            },
+        })
     }
 }
 
@@ -185,328 +351,406 @@ impl Select {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_literal() {
+        let select = |mode, text: &str, nocase| Select::new_literal(mode, text.to_owned(), nocase).yes();
+        assert!(select(LiteralSelectMode::Contains, "bc", false).test("abcd").unwrap());
+        assert!(!select(LiteralSelectMode::Contains, "BC", false).test("abcd").unwrap());
+        assert!(select(LiteralSelectMode::Contains, "BC", true).test("abcd").unwrap());
+        assert!(select(LiteralSelectMode::Contains, "", false).test("").unwrap());
+        assert!(select(LiteralSelectMode::Prefix, "ab", false).test("abcd").unwrap());
+        assert!(!select(LiteralSelectMode::Prefix, "bc", false).test("abcd").unwrap());
+        assert!(select(LiteralSelectMode::Prefix, "AB", true).test("abcd").unwrap());
+        assert!(select(LiteralSelectMode::Suffix, "cd", false).test("abcd").unwrap());
+        assert!(!select(LiteralSelectMode::Suffix, "CD", false).test("abcd").unwrap());
+        assert!(select(LiteralSelectMode::Suffix, "ÇD", true).test("abçd").unwrap());
+        assert!(select(LiteralSelectMode::Eq, "abcd", false).test("abcd").unwrap());
+        assert!(!select(LiteralSelectMode::Eq, "abc", false).test("abcd").unwrap());
+        assert!(select(LiteralSelectMode::Eq, "ABCD", true).test("abcd").unwrap());
+        assert!(select(LiteralSelectMode::Eq, "", false).test("").unwrap());
+        // not
+        assert!(Select::new_literal(LiteralSelectMode::Contains, "x".to_owned(), false).not().test("abcd").unwrap());
+        // 全局配置
+        let configs = [Config::Nocase];
+        let cond = select(LiteralSelectMode::Contains, "BC", false).with_configs(&configs).unwrap();
+        assert!(cond.test("abcd").unwrap());
+        assert_eq!(cond, select(LiteralSelectMode::Contains, "bc", true));
+        assert!(!select(LiteralSelectMode::Contains, "BC", false).with_configs(&[]).unwrap().test("abcd").unwrap());
+    }
+
+    #[test]
+    fn test_reg_find() {
+        assert!(Select::new_reg_find("[", false).is_err());
+        assert!(Select::new_reg_find(r"\d+", false).unwrap().yes().test("ab12cd").unwrap());
+        assert!(!Select::new_reg_find(r"\d+", false).unwrap().yes().test("abcd").unwrap());
+        assert!(!Select::new_reg_find("B", false).unwrap().yes().test("abcd").unwrap());
+        assert!(Select::new_reg_find("B", true).unwrap().yes().test("abcd").unwrap());
+        assert!(Select::new_reg_find("B", false).unwrap().not().test("abcd").unwrap());
+        let cond = Select::new_reg_find("B", false).unwrap().yes().not().with_configs(&[Config::Nocase]).unwrap();
+        assert!(!cond.test("abcd").unwrap());
+    }
+
+    #[test]
+    fn test_json() {
+        let json = |path: &str, cond| Select::new_json(JsonPath::new(path.to_string(), "json").unwrap(), cond).yes();
+        let num = Select::new_num_range(Some(Num::from(200)), Some(Num::from(299))).yes();
+        assert!(json(".status", num.clone()).test(r#"{"status": 204}"#).unwrap());
+        assert!(json(".status", num.clone()).test(r#"{"status": "200"}"#).unwrap());
+        assert!(!json(".status", num.clone()).test(r#"{"status": 404}"#).unwrap());
+        assert!(!json(".code", num.clone()).test(r#"{"status": 204}"#).unwrap());
+        let not_empty = Select::Text { mode: TextSelectMode::Empty }.not();
+        assert!(json(".tags[1]", not_empty).test(r#"{"tags": ["a", "b"]}"#).unwrap());
+        let cond = json(".name", Select::new_literal(LiteralSelectMode::Eq, "ABC".to_owned(), false).yes());
+        assert!(!cond.test(r#"{"name": "abc"}"#).unwrap());
+        let cond = cond.with_configs(&[Config::Nocase]).unwrap();
+        assert!(cond.test(r#"{"name": "abc"}"#).unwrap());
+        // 不是有效的JSON时返回错误，不受not影响
+        assert!(matches!(cond.test("not json"), Err(RpErr::ParseJsonErr { .. })));
+        assert!(matches!(cond.not().test("not json"), Err(RpErr::ParseJsonErr { .. })));
+    }
+
+    #[test]
+    fn test_and_or_not() {
+        let upper = || Select::Text { mode: TextSelectMode::Upper }.yes();
+        let len3 = || Select::TextLenSpec { spec: 3 }.yes();
+        let empty = || Select::Text { mode: TextSelectMode::Empty }.yes();
+        let and = Condition::new_and(vec![upper(), len3()]);
+        assert!(and.test("ABC").unwrap());
+        assert!(!and.test("AB").unwrap());
+        assert!(!and.test("abc").unwrap());
+        let or = Condition::new_or(vec![and.clone(), empty()]);
+        assert!(or.test("ABC").unwrap());
+        assert!(or.test("").unwrap());
+        assert!(!or.test("abc").unwrap());
+        assert!(or.clone().not().test("abc").unwrap());
+        assert!(!or.not().test("").unwrap());
+        assert!(upper().not().not().test("ABC").unwrap());
+        assert_eq!(Condition::new_and(vec![upper()]), upper());
+        assert_eq!(Condition::new_or(vec![upper()]), upper());
+    }
+
     #[test]
     fn test_text_len_range() {
-        assert!(!Select::new_text_len_range(Some(3), Some(5)).yes().test("12"));
-        assert!(Select::new_text_len_range(Some(3), Some(5)).yes().test("123"));
-        assert!(Select::new_text_len_range(Some(3), Some(5)).yes().test("1234"));
-        assert!(Select::new_text_len_range(Some(3), Some(5)).yes().test("12345"));
-        assert!(!Select::new_text_len_range(Some(3), Some(5)).yes().test("123456"));
-        assert!(!Select::new_text_len_range(Some(3), None).yes().test("12"));
-        assert!(Select::new_text_len_range(Some(3), None).yes().test("123"));
-        assert!(Select::new_text_len_range(Some(3), None).yes().test("1234"));
-        assert!(Select::new_text_len_range(None, Some(3)).yes().test("12"));
-        assert!(Select::new_text_len_range(None, Some(3)).yes().test("123"));
-        assert!(!Select::new_text_len_range(None, Some(3)).yes().test("1234"));
-        assert!(Select::new_text_len_range(None, None).yes().test("123"));
+        assert!(!Select::new_text_len_range(Some(3), Some(5)).yes().test("12").unwrap());
+        assert!(Select::new_text_len_range(Some(3), Some(5)).yes().test("123").unwrap());
+        assert!(Select::new_text_len_range(Some(3), Some(5)).yes().test("1234").unwrap());
+        assert!(Select::new_text_len_range(Some(3), Some(5)).yes().test("12345").unwrap());
+        assert!(!Select::new_text_len_range(Some(3), Some(5)).yes().test("123456").unwrap());
+        assert!(!Select::new_text_len_range(Some(3), None).yes().test("12").unwrap());
+        assert!(Select::new_text_len_range(Some(3), None).yes().test("123").unwrap());
+        assert!(Select::new_text_len_range(Some(3), None).yes().test("1234").unwrap());
+        assert!(Select::new_text_len_range(None, Some(3)).yes().test("12").unwrap());
+        assert!(Select::new_text_len_range(None, Some(3)).yes().test("123").unwrap());
+        assert!(!Select::new_text_len_range(None, Some(3)).yes().test("1234").unwrap());
+        assert!(Select::new_text_len_range(None, None).yes().test("123").unwrap());
         // not
-        assert!(Select::new_text_len_range(Some(3), Some(5)).not().test("12"));
-        assert!(!Select::new_text_len_range(Some(3), Some(5)).not().test("123"));
-        assert!(!Select::new_text_len_range(Some(3), Some(5)).not().test("1234"));
-        assert!(!Select::new_text_len_range(Some(3), Some(5)).not().test("12345"));
-        assert!(Select::new_text_len_range(Some(3), Some(5)).not().test("123456"));
-        assert!(Select::new_text_len_range(Some(3), None).not().test("12"));
-        assert!(!Select::new_text_len_range(Some(3), None).not().test("123"));
-        assert!(!Select::new_text_len_range(Some(3), None).not().test("1234"));
-        assert!(!Select::new_text_len_range(None, Some(3)).not().test("12"));
-        assert!(!Select::new_text_len_range(None, Some(3)).not().test("123"));
-        assert!(Select::new_text_len_range(None, Some(3)).not().test("1234"));
-        assert!(!Select::new_text_len_range(None, None).not().test("123"));
+        assert!(Select::new_text_len_range(Some(3), Some(5)).not().test("12").unwrap());
+        assert!(!Select::new_text_len_range(Some(3), Some(5)).not().test("123").unwrap());
+        assert!(!Select::new_text_len_range(Some(3), Some(5)).not().test("1234").unwrap());
+        assert!(!Select::new_text_len_range(Some(3), Some(5)).not().test("12345").unwrap());
+        assert!(Select::new_text_len_range(Some(3), Some(5)).not().test("123456").unwrap());
+        assert!(Select::new_text_len_range(Some(3), None).not().test("12").unwrap());
+        assert!(!Select::new_text_len_range(Some(3), None).not().test("123").unwrap());
+        assert!(!Select::new_text_len_range(Some(3), None).not().test("1234").unwrap());
+        assert!(!Select::new_text_len_range(None, Some(3)).not().test("12").unwrap());
+        assert!(!Select::new_text_len_range(None, Some(3)).not().test("123").unwrap());
+        assert!(Select::new_text_len_range(None, Some(3)).not().test("1234").unwrap());
+        assert!(!Select::new_text_len_range(None, None).not().test("123").unwrap());
     }
 
     #[test]
     fn test_text_len_spec() {
-        assert!(Select::TextLenSpec { spec: 0 }.yes().test(""));
-        assert!(!Select::TextLenSpec { spec: 0 }.yes().test("1"));
-        assert!(!Select::TextLenSpec { spec: 3 }.yes().test(""));
-        assert!(!Select::TextLenSpec { spec: 3 }.yes().test("12"));
-        assert!(Select::TextLenSpec { spec: 3 }.yes().test("123"));
-        assert!(!Select::TextLenSpec { spec: 3 }.yes().test("1234"));
+        assert!(Select::TextLenSpec { spec: 0 }.yes().test("").unwrap());
+        assert!(!Select::TextLenSpec { spec: 0 }.yes().test("1").unwrap());
+        assert!(!Select::TextLenSpec { spec: 3 }.yes().test("").unwrap());
+        assert!(!Select::TextLenSpec { spec: 3 }.yes().test("12").unwrap());
+        assert!(Select::TextLenSpec { spec: 3 }.yes().test("123").unwrap());
+        assert!(!Select::TextLenSpec { spec: 3 }.yes().test("1234").unwrap());
         // not
-        assert!(!Select::TextLenSpec { spec: 0 }.not().test(""));
-        assert!(Select::TextLenSpec { spec: 0 }.not().test("1"));
-        assert!(Select::TextLenSpec { spec: 3 }.not().test(""));
-        assert!(Select::TextLenSpec { spec: 3 }.not().test("12"));
-        assert!(!Select::TextLenSpec { spec: 3 }.not().test("123"));
-        assert!(Select::TextLenSpec { spec: 3 }.not().test("1234"));
+        assert!(!Select::TextLenSpec { spec: 0 }.not().test("").unwrap());
+        assert!(Select::TextLenSpec { spec: 0 }.not().test("1").unwrap());
+        assert!(Select::TextLenSpec { spec: 3 }.not().test("").unwrap());
+        assert!(Select::TextLenSpec { spec: 3 }.not().test("12").unwrap());
+        assert!(!Select::TextLenSpec { spec: 3 }.not().test("123").unwrap());
+        assert!(Select::TextLenSpec { spec: 3 }.not().test("1234").unwrap());
     }
 
     #[test]
     fn test_integer_range() {
-        assert!(!Select::new_num_range(Some(Num::from(3)), Some(Num::from(5))).yes().test("2"));
-        assert!(Select::new_num_range(Some(Num::from(3)), Some(Num::from(5))).yes().test("3"));
-        assert!(Select::new_num_range(Some(Num::from(3)), Some(Num::from(5))).yes().test("4"));
-        assert!(Select::new_num_range(Some(Num::from(3)), Some(Num::from(5))).yes().test("5"));
-        assert!(!Select::new_num_range(Some(Num::from(3)), Some(Num::from(5))).yes().test("6"));
-        assert!(!Select::new_num_range(Some(Num::from(3)), None).yes().test("2"));
-        assert!(Select::new_num_range(Some(Num::from(3)), None).yes().test("3"));
-        assert!(Select::new_num_range(Some(Num::from(3)), None).yes().test("4"));
-        assert!(Select::new_num_range(None, Some(Num::from(3))).yes().test("2"));
-        assert!(Select::new_num_range(None, Some(Num::from(3))).yes().test("3"));
-        assert!(!Select::new_num_range(None, Some(Num::from(3))).yes().test("4"));
-        assert!(Select::new_num_range(None, None).yes().test("3"));
-        assert!(!Select::new_num_range(None, None).yes().test("abc"));
-        assert!(!Select::new_num_range(None, None).yes().test(""));
+        assert!(!Select::new_num_range(Some(Num::from(3)), Some(Num::from(5))).yes().test("2").unwrap());
+        assert!(Select::new_num_range(Some(Num::from(3)), Some(Num::from(5))).yes().test("3").unwrap());
+        assert!(Select::new_num_range(Some(Num::from(3)), Some(Num::from(5))).yes().test("4").unwrap());
+        assert!(Select::new_num_range(Some(Num::from(3)), Some(Num::from(5))).yes().test("5").unwrap());
+        assert!(!Select::new_num_range(Some(Num::from(3)), Some(Num::from(5))).yes().test("6").unwrap());
+        assert!(!Select::new_num_range(Some(Num::from(3)), None).yes().test("2").unwrap());
+        assert!(Select::new_num_range(Some(Num::from(3)), None).yes().test("3").unwrap());
+        assert!(Select::new_num_range(Some(Num::from(3)), None).yes().test("4").unwrap());
+        assert!(Select::new_num_range(None, Some(Num::from(3))).yes().test("2").unwrap());
+        assert!(Select::new_num_range(None, Some(Num::from(3))).yes().test("3").unwrap());
+        assert!(!Select::new_num_range(None, Some(Num::from(3))).yes().test("4").unwrap());
+        assert!(Select::new_num_range(None, None).yes().test("3").unwrap());
+        assert!(!Select::new_num_range(None, None).yes().test("abc").unwrap());
+        assert!(!Select::new_num_range(None, None).yes().test("").unwrap());
         // not
-        assert!(Select::new_num_range(Some(Num::from(3)), Some(Num::from(5))).not().test("2"));
-        assert!(!Select::new_num_range(Some(Num::from(3)), Some(Num::from(5))).not().test("3"));
-        assert!(!Select::new_num_range(Some(Num::from(3)), Some(Num::from(5))).not().test("4"));
-        assert!(!Select::new_num_range(Some(Num::from(3)), Some(Num::from(5))).not().test("5"));
-        assert!(Select::new_num_range(Some(Num::from(3)), Some(Num::from(5))).not().test("6"));
-        assert!(Select::new_num_range(Some(Num::from(3)), None).not().test("2"));
-        assert!(!Select::new_num_range(Some(Num::from(3)), None).not().test("3"));
-        assert!(!Select::new_num_range(Some(Num::from(3)), None).not().test("4"));
-        assert!(!Select::new_num_range(None, Some(Num::from(3))).not().test("2"));
-        assert!(!Select::new_num_range(None, Some(Num::from(3))).not().test("3"));
-        assert!(Select::new_num_range(None, Some(Num::from(3))).not().test("4"));
-        assert!(!Select::new_num_range(None, None).not().test("3"));
-        assert!(Select::new_num_range(None, None).not().test("abc"));
-        assert!(Select::new_num_range(None, None).not().test(""));
+        assert!(Select::new_num_range(Some(Num::from(3)), Some(Num::from(5))).not().test("2").unwrap());
+        assert!(!Select::new_num_range(Some(Num::from(3)), Some(Num::from(5))).not().test("3").unwrap());
+        assert!(!Select::new_num_range(Some(Num::from(3)), Some(Num::from(5))).not().test("4").unwrap());
+        assert!(!Select::new_num_range(Some(Num::from(3)), Some(Num::from(5))).not().test("5").unwrap());
+        assert!(Select::new_num_range(Some(Num::from(3)), Some(Num::from(5))).not().test("6").unwrap());
+        assert!(Select::new_num_range(Some(Num::from(3)), None).not().test("2").unwrap());
+        assert!(!Select::new_num_range(Some(Num::from(3)), None).not().test("3").unwrap());
+        assert!(!Select::new_num_range(Some(Num::from(3)), None).not().test("4").unwrap());
+        assert!(!Select::new_num_range(None, Some(Num::from(3))).not().test("2").unwrap());
+        assert!(!Select::new_num_range(None, Some(Num::from(3))).not().test("3").unwrap());
+        assert!(Select::new_num_range(None, Some(Num::from(3))).not().test("4").unwrap());
+        assert!(!Select::new_num_range(None, None).not().test("3").unwrap());
+        assert!(Select::new_num_range(None, None).not().test("abc").unwrap());
+        assert!(Select::new_num_range(None, None).not().test("").unwrap());
     }
 
     #[test]
     fn test_integer_spec() {
-        assert!(Select::NumSpec { spec: Num::from(0) }.yes().test("0"));
-        assert!(!Select::NumSpec { spec: Num::from(0) }.yes().test("1"));
-        assert!(!Select::NumSpec { spec: Num::from(3) }.yes().test("1"));
-        assert!(Select::NumSpec { spec: Num::from(3) }.yes().test("3"));
-        assert!(!Select::NumSpec { spec: Num::from(3) }.yes().test("abc"));
-        assert!(!Select::NumSpec { spec: Num::from(3) }.yes().test(""));
+        assert!(Select::NumSpec { spec: Num::from(0) }.yes().test("0").unwrap());
+        assert!(!Select::NumSpec { spec: Num::from(0) }.yes().test("1").unwrap());
+        assert!(!Select::NumSpec { spec: Num::from(3) }.yes().test("1").unwrap());
+        assert!(Select::NumSpec { spec: Num::from(3) }.yes().test("3").unwrap());
+        assert!(!Select::NumSpec { spec: Num::from(3) }.yes().test("abc").unwrap());
+        assert!(!Select::NumSpec { spec: Num::from(3) }.yes().test("").unwrap());
         // not
-        assert!(!Select::NumSpec { spec: Num::from(0) }.not().test("0"));
-        assert!(Select::NumSpec { spec: Num::from(0) }.not().test("1"));
-        assert!(Select::NumSpec { spec: Num::from(3) }.not().test("1"));
-        assert!(!Select::NumSpec { spec: Num::from(3) }.not().test("3"));
-        assert!(Select::NumSpec { spec: Num::from(3) }.not().test("abc"));
-        assert!(Select::NumSpec { spec: Num::from(3) }.not().test(""));
+        assert!(!Select::NumSpec { spec: Num::from(0) }.not().test("0").unwrap());
+        assert!(Select::NumSpec { spec: Num::from(0) }.not().test("1").unwrap());
+        assert!(Select::NumSpec { spec: Num::from(3) }.not().test("1").unwrap());
+        assert!(!Select::NumSpec { spec: Num::from(3) }.not().test("3").unwrap());
+        assert!(Select::NumSpec { spec: Num::from(3) }.not().test("abc").unwrap());
+        assert!(Select::NumSpec { spec: Num::from(3) }.not().test("").unwrap());
     }
 
     #[test]
     fn test_float_range() {
-        assert!(!Select::new_num_range(Some(Num::from(3.0)), Some(Num::from(5.0))).yes().test("2"));
-        assert!(Select::new_num_range(Some(Num::from(3.0)), Some(Num::from(5.0))).yes().test("3"));
-        assert!(Select::new_num_range(Some(Num::from(3.0)), Some(Num::from(5.0))).yes().test("4"));
-        assert!(Select::new_num_range(Some(Num::from(3.0)), Some(Num::from(5.0))).yes().test("5"));
-        assert!(!Select::new_num_range(Some(Num::from(3.0)), Some(Num::from(5.0))).yes().test("6"));
-        assert!(!Select::new_num_range(Some(Num::from(3.0)), None).yes().test("2"));
-        assert!(Select::new_num_range(Some(Num::from(3.0)), None).yes().test("3"));
-        assert!(Select::new_num_range(Some(Num::from(3.0)), None).yes().test("4"));
-        assert!(Select::new_num_range(None, Some(Num::from(3.0))).yes().test("2"));
-        assert!(Select::new_num_range(None, Some(Num::from(3.0))).yes().test("3"));
-        assert!(!Select::new_num_range(None, Some(Num::from(3.0))).yes().test("4"));
-        assert!(Select::new_num_range(None, None).yes().test("3"));
-        assert!(!Select::new_num_range(None, None).yes().test("abc"));
-        assert!(!Select::new_num_range(None, None).yes().test("NaN"));
-        assert!(!Select::new_num_range(None, None).yes().test("nan"));
-        assert!(!Select::new_num_range(None, None).yes().test("inf"));
-        assert!(!Select::new_num_range(None, None).yes().test("Inf"));
-        assert!(!Select::new_num_range(None, None).yes().test("-inf"));
-        assert!(!Select::new_num_range(None, None).yes().test("-Inf"));
-        assert!(!Select::new_num_range(None, None).yes().test(""));
+        assert!(!Select::new_num_range(Some(Num::from(3.0)), Some(Num::from(5.0))).yes().test("2").unwrap());
+        assert!(Select::new_num_range(Some(Num::from(3.0)), Some(Num::from(5.0))).yes().test("3").unwrap());
+        assert!(Select::new_num_range(Some(Num::from(3.0)), Some(Num::from(5.0))).yes().test("4").unwrap());
+        assert!(Select::new_num_range(Some(Num::from(3.0)), Some(Num::from(5.0))).yes().test("5").unwrap());
+        assert!(!Select::new_num_range(Some(Num::from(3.0)), Some(Num::from(5.0))).yes().test("6").unwrap());
+        assert!(!Select::new_num_range(Some(Num::from(3.0)), None).yes().test("2").unwrap());
+        assert!(Select::new_num_range(Some(Num::from(3.0)), None).yes().test("3").unwrap());
+        assert!(Select::new_num_range(Some(Num::from(3.0)), None).yes().test("4").unwrap());
+        assert!(Select::new_num_range(None, Some(Num::from(3.0))).yes().test("2").unwrap());
+        assert!(Select::new_num_range(None, Some(Num::from(3.0))).yes().test("3").unwrap());
+        assert!(!Select::new_num_range(None, Some(Num::from(3.0))).yes().test("4").unwrap());
+        assert!(Select::new_num_range(None, None).yes().test("3").unwrap());
+        assert!(!Select::new_num_range(None, None).yes().test("abc").unwrap());
+        assert!(!Select::new_num_range(None, None).yes().test("NaN").unwrap());
+        assert!(!Select::new_num_range(None, None).yes().test("nan").unwrap());
+        assert!(!Select::new_num_range(None, None).yes().test("inf").unwrap());
+        assert!(!Select::new_num_range(None, None).yes().test("Inf").unwrap());
+        assert!(!Select::new_num_range(None, None).yes().test("-inf").unwrap());
+        assert!(!Select::new_num_range(None, None).yes().test("-Inf").unwrap());
+        assert!(!Select::new_num_range(None, None).yes().test("").unwrap());
         // not
-        assert!(Select::new_num_range(Some(Num::from(3.0)), Some(Num::from(5.0))).not().test("2"));
-        assert!(!Select::new_num_range(Some(Num::from(3.0)), Some(Num::from(5.0))).not().test("3"));
-        assert!(!Select::new_num_range(Some(Num::from(3.0)), Some(Num::from(5.0))).not().test("4"));
-        assert!(!Select::new_num_range(Some(Num::from(3.0)), Some(Num::from(5.0))).not().test("5"));
-        assert!(Select::new_num_range(Some(Num::from(3.0)), Some(Num::from(5.0))).not().test("6"));
-        assert!(Select::new_num_range(Some(Num::from(3.0)), None).not().test("2"));
-        assert!(!Select::new_num_range(Some(Num::from(3.0)), None).not().test("3"));
-        assert!(!Select::new_num_range(Some(Num::from(3.0)), None).not().test("4"));
-        assert!(!Select::new_num_range(None, Some(Num::from(3.0))).not().test("2"));
-        assert!(!Select::new_num_range(None, Some(Num::from(3.0))).not().test("3"));
-        assert!(Select::new_num_range(None, Some(Num::from(3.0))).not().test("4"));
-        assert!(!Select::new_num_range(None, None).not().test("3"));
-        assert!(Select::new_num_range(None, None).not().test("abc"));
-        assert!(Select::new_num_range(None, None).not().test("NaN"));
-        assert!(Select::new_num_range(None, None).not().test("nan"));
-        assert!(Select::new_num_range(None, None).not().test("inf"));
-        assert!(Select::new_num_range(None, None).not().test("Inf"));
-        assert!(Select::new_num_range(None, None).not().test("-inf"));
-        assert!(Select::new_num_range(None, None).not().test("-Inf"));
-        assert!(Select::new_num_range(None, None).not().test(""));
+        assert!(Select::new_num_range(Some(Num::from(3.0)), Some(Num::from(5.0))).not().test("2").unwrap());
+        assert!(!Select::new_num_range(Some(Num::from(3.0)), Some(Num::from(5.0))).not().test("3").unwrap());
+        assert!(!Select::new_num_range(Some(Num::from(3.0)), Some(Num::from(5.0))).not().test("4").unwrap());
+        assert!(!Select::new_num_range(Some(Num::from(3.0)), Some(Num::from(5.0))).not().test("5").unwrap());
+        assert!(Select::new_num_range(Some(Num::from(3.0)), Some(Num::from(5.0))).not().test("6").unwrap());
+        assert!(Select::new_num_range(Some(Num::from(3.0)), None).not().test("2").unwrap());
+        assert!(!Select::new_num_range(Some(Num::from(3.0)), None).not().test("3").unwrap());
+        assert!(!Select::new_num_range(Some(Num::from(3.0)), None).not().test("4").unwrap());
+        assert!(!Select::new_num_range(None, Some(Num::from(3.0))).not().test("2").unwrap());
+        assert!(!Select::new_num_range(None, Some(Num::from(3.0))).not().test("3").unwrap());
+        assert!(Select::new_num_range(None, Some(Num::from(3.0))).not().test("4").unwrap());
+        assert!(!Select::new_num_range(None, None).not().test("3").unwrap());
+        assert!(Select::new_num_range(None, None).not().test("abc").unwrap());
+        assert!(Select::new_num_range(None, None).not().test("NaN").unwrap());
+        assert!(Select::new_num_range(None, None).not().test("nan").unwrap());
+        assert!(Select::new_num_range(None, None).not().test("inf").unwrap());
+        assert!(Select::new_num_range(None, None).not().test("Inf").unwrap());
+        assert!(Select::new_num_range(None, None).not().test("-inf").unwrap());
+        assert!(Select::new_num_range(None, None).not().test("-Inf").unwrap());
+        assert!(Select::new_num_range(None, None).not().test("").unwrap());
     }
 
     #[test]
     fn test_float_spec() {
-        assert!(Select::NumSpec { spec: Num::from(0.0) }.yes().test("0"));
-        assert!(!Select::NumSpec { spec: Num::from(0.0) }.yes().test("1"));
-        assert!(!Select::NumSpec { spec: Num::from(3.0) }.yes().test("1"));
-        assert!(Select::NumSpec { spec: Num::from(3.0) }.yes().test("3"));
-        assert!(!Select::NumSpec { spec: Num::from(3.0) }.yes().test("abc"));
-        assert!(!Select::NumSpec { spec: Num::from(3.0) }.yes().test("NaN"));
-        assert!(!Select::NumSpec { spec: Num::from(3.0) }.yes().test("nan"));
-        assert!(!Select::NumSpec { spec: Num::from(3.0) }.yes().test("inf"));
-        assert!(!Select::NumSpec { spec: Num::from(3.0) }.yes().test("Inf"));
-        assert!(!Select::NumSpec { spec: Num::from(3.0) }.yes().test("-inf"));
-        assert!(!Select::NumSpec { spec: Num::from(3.0) }.yes().test("-Inf"));
-        assert!(!Select::NumSpec { spec: Num::from(3.0) }.yes().test(""));
+        assert!(Select::NumSpec { spec: Num::from(0.0) }.yes().test("0").unwrap());
+        assert!(!Select::NumSpec { spec: Num::from(0.0) }.yes().test("1").unwrap());
+        assert!(!Select::NumSpec { spec: Num::from(3.0) }.yes().test("1").unwrap());
+        assert!(Select::NumSpec { spec: Num::from(3.0) }.yes().test("3").unwrap());
+        assert!(!Select::NumSpec { spec: Num::from(3.0) }.yes().test("abc").unwrap());
+        assert!(!Select::NumSpec { spec: Num::from(3.0) }.yes().test("NaN").unwrap());
+        assert!(!Select::NumSpec { spec: Num::from(3.0) }.yes().test("nan").unwrap());
+        assert!(!Select::NumSpec { spec: Num::from(3.0) }.yes().test("inf").unwrap());
+        assert!(!Select::NumSpec { spec: Num::from(3.0) }.yes().test("Inf").unwrap());
+        assert!(!Select::NumSpec { spec: Num::from(3.0) }.yes().test("-inf").unwrap());
+        assert!(!Select::NumSpec { spec: Num::from(3.0) }.yes().test("-Inf").unwrap());
+        assert!(!Select::NumSpec { spec: Num::from(3.0) }.yes().test("").unwrap());
         // not
-        assert!(!Select::NumSpec { spec: Num::from(0.0) }.not().test("0"));
-        assert!(Select::NumSpec { spec: Num::from(0.0) }.not().test("1"));
-        assert!(Select::NumSpec { spec: Num::from(3.0) }.not().test("1"));
-        assert!(!Select::NumSpec { spec: Num::from(3.0) }.not().test("3"));
-        assert!(Select::NumSpec { spec: Num::from(3.0) }.not().test("abc"));
-        assert!(Select::NumSpec { spec: Num::from(3.0) }.not().test("NaN"));
-        assert!(Select::NumSpec { spec: Num::from(3.0) }.not().test("nan"));
-        assert!(Select::NumSpec { spec: Num::from(3.0) }.not().test("inf"));
-        assert!(Select::NumSpec { spec: Num::from(3.0) }.not().test("Inf"));
-        assert!(Select::NumSpec { spec: Num::from(3.0) }.not().test("-inf"));
-        assert!(Select::NumSpec { spec: Num::from(3.0) }.not().test("-Inf"));
-        assert!(Select::NumSpec { spec: Num::from(3.0) }.not().test(""));
+        assert!(!Select::NumSpec { spec: Num::from(0.0) }.not().test("0").unwrap());
+        assert!(Select::NumSpec { spec: Num::from(0.0) }.not().test("1").unwrap());
+        assert!(Select::NumSpec { spec: Num::from(3.0) }.not().test("1").unwrap());
+        assert!(!Select::NumSpec { spec: Num::from(3.0) }.not().test("3").unwrap());
+        assert!(Select::NumSpec { spec: Num::from(3.0) }.not().test("abc").unwrap());
+        assert!(Select::NumSpec { spec: Num::from(3.0) }.not().test("NaN").unwrap());
+        assert!(Select::NumSpec { spec: Num::from(3.0) }.not().test("nan").unwrap());
+        assert!(Select::NumSpec { spec: Num::from(3.0) }.not().test("inf").unwrap());
+        assert!(Select::NumSpec { spec: Num::from(3.0) }.not().test("Inf").unwrap());
+        assert!(Select::NumSpec { spec: Num::from(3.0) }.not().test("-inf").unwrap());
+        assert!(Select::NumSpec { spec: Num::from(3.0) }.not().test("-Inf").unwrap());
+        assert!(Select::NumSpec { spec: Num::from(3.0) }.not().test("").unwrap());
     }
 
     #[test]
     fn test_num() {
         // integer
-        assert!(!Select::Num { integer: Some(true) }.yes().test("abc"));
-        assert!(Select::Num { integer: Some(true) }.yes().test("123"));
-        assert!(!Select::Num { integer: Some(true) }.yes().test("123.1"));
-        assert!(!Select::Num { integer: Some(true) }.yes().test("123.0"));
-        assert!(!Select::Num { integer: Some(true) }.yes().test("NaN"));
-        assert!(!Select::Num { integer: Some(true) }.yes().test("nan"));
-        assert!(!Select::Num { integer: Some(true) }.yes().test("inf"));
-        assert!(!Select::Num { integer: Some(true) }.yes().test("Inf"));
-        assert!(!Select::Num { integer: Some(true) }.yes().test("-inf"));
-        assert!(!Select::Num { integer: Some(true) }.yes().test("-Inf"));
-        assert!(!Select::Num { integer: Some(true) }.yes().test(""));
-        assert!(Select::Num { integer: Some(true) }.not().test("abc"));
-        assert!(!Select::Num { integer: Some(true) }.not().test("123"));
-        assert!(Select::Num { integer: Some(true) }.not().test("123.1"));
-        assert!(Select::Num { integer: Some(true) }.not().test("123.0"));
-        assert!(Select::Num { integer: Some(true) }.not().test("NaN"));
-        assert!(Select::Num { integer: Some(true) }.not().test("nan"));
-        assert!(Select::Num { integer: Some(true) }.not().test("inf"));
-        assert!(Select::Num { integer: Some(true) }.not().test("Inf"));
-        assert!(Select::Num { integer: Some(true) }.not().test("-inf"));
-        assert!(Select::Num { integer: Some(true) }.not().test("-Inf"));
-        assert!(Select::Num { integer: Some(true) }.not().test(""));
+        assert!(!Select::Num { integer: Some(true) }.yes().test("abc").unwrap());
+        assert!(Select::Num { integer: Some(true) }.yes().test("123").unwrap());
+        assert!(!Select::Num { integer: Some(true) }.yes().test("123.1").unwrap());
+        assert!(!Select::Num { integer: Some(true) }.yes().test("123.0").unwrap());
+        assert!(!Select::Num { integer: Some(true) }.yes().test("NaN").unwrap());
+        assert!(!Select::Num { integer: Some(true) }.yes().test("nan").unwrap());
+        assert!(!Select::Num { integer: Some(true) }.yes().test("inf").unwrap());
+        assert!(!Select::Num { integer: Some(true) }.yes().test("Inf").unwrap());
+        assert!(!Select::Num { integer: Some(true) }.yes().test("-inf").unwrap());
+        assert!(!Select::Num { integer: Some(true) }.yes().test("-Inf").unwrap());
+        assert!(!Select::Num { integer: Some(true) }.yes().test("").unwrap());
+        assert!(Select::Num { integer: Some(true) }.not().test("abc").unwrap());
+        assert!(!Select::Num { integer: Some(true) }.not().test("123").unwrap());
+        assert!(Select::Num { integer: Some(true) }.not().test("123.1").unwrap());
+        assert!(Select::Num { integer: Some(true) }.not().test("123.0").unwrap());
+        assert!(Select::Num { integer: Some(true) }.not().test("NaN").unwrap());
+        assert!(Select::Num { integer: Some(true) }.not().test("nan").unwrap());
+        assert!(Select::Num { integer: Some(true) }.not().test("inf").unwrap());
+        assert!(Select::Num { integer: Some(true) }.not().test("Inf").unwrap());
+        assert!(Select::Num { integer: Some(true) }.not().test("-inf").unwrap());
+        assert!(Select::Num { integer: Some(true) }.not().test("-Inf").unwrap());
+        assert!(Select::Num { integer: Some(true) }.not().test("").unwrap());
         // float
-        assert!(!Select::Num { integer: Some(false) }.yes().test("abc"));
-        assert!(!Select::Num { integer: Some(false) }.yes().test("123"));
-        assert!(Select::Num { integer: Some(false) }.yes().test("123.1"));
-        assert!(Select::Num { integer: Some(false) }.yes().test("123.0"));
-        assert!(!Select::Num { integer: Some(false) }.yes().test("NaN"));
-        assert!(!Select::Num { integer: Some(false) }.yes().test("nan"));
-        assert!(!Select::Num { integer: Some(false) }.yes().test("inf"));
-        assert!(!Select::Num { integer: Some(false) }.yes().test("Inf"));
-        assert!(!Select::Num { integer: Some(false) }.yes().test("-inf"));
-        assert!(!Select::Num { integer: Some(false) }.yes().test("-Inf"));
-        assert!(!Select::Num { integer: Some(false) }.yes().test(""));
-        assert!(Select::Num { integer: Some(false) }.not().test("abc"));
-        assert!(Select::Num { integer: Some(false) }.not().test("123"));
-        assert!(!Select::Num { integer: Some(false) }.not().test("123.1"));
-        assert!(!Select::Num { integer: Some(false) }.not().test("123.0"));
-        assert!(Select::Num { integer: Some(false) }.not().test("NaN"));
-        assert!(Select::Num { integer: Some(false) }.not().test("nan"));
-        assert!(Select::Num { integer: Some(false) }.not().test("inf"));
-        assert!(Select::Num { integer: Some(false) }.not().test("Inf"));
-        assert!(Select::Num { integer: Some(false) }.not().test("-inf"));
-        assert!(Select::Num { integer: Some(false) }.not().test("-Inf"));
-        assert!(Select::Num { integer: Some(false) }.not().test(""));
+        assert!(!Select::Num { integer: Some(false) }.yes().test("abc").unwrap());
+        assert!(!Select::Num { integer: Some(false) }.yes().test("123").unwrap());
+        assert!(Select::Num { integer: Some(false) }.yes().test("123.1").unwrap());
+        assert!(Select::Num { integer: Some(false) }.yes().test("123.0").unwrap());
+        assert!(!Select::Num { integer: Some(false) }.yes().test("NaN").unwrap());
+        assert!(!Select::Num { integer: Some(false) }.yes().test("nan").unwrap());
+        assert!(!Select::Num { integer: Some(false) }.yes().test("inf").unwrap());
+        assert!(!Select::Num { integer: Some(false) }.yes().test("Inf").unwrap());
+        assert!(!Select::Num { integer: Some(false) }.yes().test("-inf").unwrap());
+        assert!(!Select::Num { integer: Some(false) }.yes().test("-Inf").unwrap());
+        assert!(!Select::Num { integer: Some(false) }.yes().test("").unwrap());
+        assert!(Select::Num { integer: Some(false) }.not().test("abc").unwrap());
+        assert!(Select::Num { integer: Some(false) }.not().test("123").unwrap());
+        assert!(!Select::Num { integer: Some(false) }.not().test("123.1").unwrap());
+        assert!(!Select::Num { integer: Some(false) }.not().test("123.0").unwrap());
+        assert!(Select::Num { integer: Some(false) }.not().test("NaN").unwrap());
+        assert!(Select::Num { integer: Some(false) }.not().test("nan").unwrap());
+        assert!(Select::Num { integer: Some(false) }.not().test("inf").unwrap());
+        assert!(Select::Num { integer: Some(false) }.not().test("Inf").unwrap());
+        assert!(Select::Num { integer: Some(false) }.not().test("-inf").unwrap());
+        assert!(Select::Num { integer: Some(false) }.not().test("-Inf").unwrap());
+        assert!(Select::Num { integer: Some(false) }.not().test("").unwrap());
         // number
-        assert!(!Select::Num { integer: None }.yes().test("abc"));
-        assert!(Select::Num { integer: None }.yes().test("123"));
-        assert!(Select::Num { integer: None }.yes().test("123.1"));
-        assert!(Select::Num { integer: None }.yes().test("123.0"));
-        assert!(!Select::Num { integer: None }.yes().test("NaN"));
-        assert!(!Select::Num { integer: None }.yes().test("nan"));
-        assert!(!Select::Num { integer: None }.yes().test("inf"));
-        assert!(!Select::Num { integer: None }.yes().test("Inf"));
-        assert!(!Select::Num { integer: None }.yes().test("-inf"));
-        assert!(!Select::Num { integer: None }.yes().test("-Inf"));
-        assert!(!Select::Num { integer: None }.yes().test(""));
-        assert!(Select::Num { integer: None }.not().test("abc"));
-        assert!(!Select::Num { integer: None }.not().test("123"));
-        assert!(!Select::Num { integer: None }.not().test("123.1"));
-        assert!(!Select::Num { integer: None }.not().test("123.0"));
-        assert!(Select::Num { integer: None }.not().test("NaN"));
-        assert!(Select::Num { integer: None }.not().test("nan"));
-        assert!(Select::Num { integer: None }.not().test("inf"));
-        assert!(Select::Num { integer: None }.not().test("Inf"));
-        assert!(Select::Num { integer: None }.not().test("-inf"));
-        assert!(Select::Num { integer: None }.not().test("-Inf"));
-        assert!(Select::Num { integer: None }.not().test(""));
+        assert!(!Select::Num { integer: None }.yes().test("abc").unwrap());
+        assert!(Select::Num { integer: None }.yes().test("123").unwrap());
+        assert!(Select::Num { integer: None }.yes().test("123.1").unwrap());
+        assert!(Select::Num { integer: None }.yes().test("123.0").unwrap());
+        assert!(!Select::Num { integer: None }.yes().test("NaN").unwrap());
+        assert!(!Select::Num { integer: None }.yes().test("nan").unwrap());
+        assert!(!Select::Num { integer: None }.yes().test("inf").unwrap());
+        assert!(!Select::Num { integer: None }.yes().test("Inf").unwrap());
+        assert!(!Select::Num { integer: None }.yes().test("-inf").unwrap());
+        assert!(!Select::Num { integer: None }.yes().test("-Inf").unwrap());
+        assert!(!Select::Num { integer: None }.yes().test("").unwrap());
+        assert!(Select::Num { integer: None }.not().test("abc").unwrap());
+        assert!(!Select::Num { integer: None }.not().test("123").unwrap());
+        assert!(!Select::Num { integer: None }.not().test("123.1").unwrap());
+        assert!(!Select::Num { integer: None }.not().test("123.0").unwrap());
+        assert!(Select::Num { integer: None }.not().test("NaN").unwrap());
+        assert!(Select::Num { integer: None }.not().test("nan").unwrap());
+        assert!(Select::Num { integer: None }.not().test("inf").unwrap());
+        assert!(Select::Num { integer: None }.not().test("Inf").unwrap());
+        assert!(Select::Num { integer: None }.not().test("-inf").unwrap());
+        assert!(Select::Num { integer: None }.not().test("-Inf").unwrap());
+        assert!(Select::Num { integer: None }.not().test("").unwrap());
     }
 
     #[test]
     fn test_text_all_case() {
         // upper
-        assert!(!Select::Text { mode: TextSelectMode::Upper }.yes().test("abc"));
-        assert!(Select::Text { mode: TextSelectMode::Upper }.yes().test("ABC"));
-        assert!(!Select::Text { mode: TextSelectMode::Upper }.yes().test("abcABC"));
-        assert!(Select::Text { mode: TextSelectMode::Upper }.yes().test("你好123.#!@"));
-        assert!(Select::Text { mode: TextSelectMode::Upper }.not().test("abc"));
-        assert!(!Select::Text { mode: TextSelectMode::Upper }.not().test("ABC"));
-        assert!(Select::Text { mode: TextSelectMode::Upper }.not().test("abcABC"));
-        assert!(!Select::Text { mode: TextSelectMode::Upper }.not().test("你好123.#!@"));
+        assert!(!Select::Text { mode: TextSelectMode::Upper }.yes().test("abc").unwrap());
+        assert!(Select::Text { mode: TextSelectMode::Upper }.yes().test("ABC").unwrap());
+        assert!(!Select::Text { mode: TextSelectMode::Upper }.yes().test("abcABC").unwrap());
+        assert!(Select::Text { mode: TextSelectMode::Upper }.yes().test("你好123.#!@").unwrap());
+        assert!(Select::Text { mode: TextSelectMode::Upper }.not().test("abc").unwrap());
+        assert!(!Select::Text { mode: TextSelectMode::Upper }.not().test("ABC").unwrap());
+        assert!(Select::Text { mode: TextSelectMode::Upper }.not().test("abcABC").unwrap());
+        assert!(!Select::Text { mode: TextSelectMode::Upper }.not().test("你好123.#!@").unwrap());
         // lower
-        assert!(Select::Text { mode: TextSelectMode::Lower }.yes().test("abc"));
-        assert!(!Select::Text { mode: TextSelectMode::Lower }.yes().test("ABC"));
-        assert!(!Select::Text { mode: TextSelectMode::Lower }.yes().test("abcABC"));
-        assert!(Select::Text { mode: TextSelectMode::Lower }.yes().test("你好123.#!@"));
-        assert!(!Select::Text { mode: TextSelectMode::Lower }.not().test("abc"));
-        assert!(Select::Text { mode: TextSelectMode::Lower }.not().test("ABC"));
-        assert!(Select::Text { mode: TextSelectMode::Lower }.not().test("abcABC"));
-        assert!(!Select::Text { mode: TextSelectMode::Lower }.not().test("你好123.#!@"));
+        assert!(Select::Text { mode: TextSelectMode::Lower }.yes().test("abc").unwrap());
+        assert!(!Select::Text { mode: TextSelectMode::Lower }.yes().test("ABC").unwrap());
+        assert!(!Select::Text { mode: TextSelectMode::Lower }.yes().test("abcABC").unwrap());
+        assert!(Select::Text { mode: TextSelectMode::Lower }.yes().test("你好123.#!@").unwrap());
+        assert!(!Select::Text { mode: TextSelectMode::Lower }.not().test("abc").unwrap());
+        assert!(Select::Text { mode: TextSelectMode::Lower }.not().test("ABC").unwrap());
+        assert!(Select::Text { mode: TextSelectMode::Lower }.not().test("abcABC").unwrap());
+        assert!(!Select::Text { mode: TextSelectMode::Lower }.not().test("你好123.#!@").unwrap());
     }
 
     #[test]
     fn test_ascii() {
-        assert!(Select::Text { mode: TextSelectMode::Ascii }.yes().test("abc"));
-        assert!(Select::Text { mode: TextSelectMode::Ascii }.yes().test(""));
-        assert!(Select::Text { mode: TextSelectMode::Ascii }.yes().test("\n"));
-        assert!(!Select::Text { mode: TextSelectMode::Ascii }.yes().test("你好"));
-        assert!(!Select::Text { mode: TextSelectMode::NonAscii }.yes().test("abc"));
-        assert!(Select::Text { mode: TextSelectMode::NonAscii }.yes().test(""));
-        assert!(!Select::Text { mode: TextSelectMode::NonAscii }.yes().test("\n"));
-        assert!(Select::Text { mode: TextSelectMode::NonAscii }.yes().test("你好"));
+        assert!(Select::Text { mode: TextSelectMode::Ascii }.yes().test("abc").unwrap());
+        assert!(Select::Text { mode: TextSelectMode::Ascii }.yes().test("").unwrap());
+        assert!(Select::Text { mode: TextSelectMode::Ascii }.yes().test("\n").unwrap());
+        assert!(!Select::Text { mode: TextSelectMode::Ascii }.yes().test("你好").unwrap());
+        assert!(!Select::Text { mode: TextSelectMode::NonAscii }.yes().test("abc").unwrap());
+        assert!(Select::Text { mode: TextSelectMode::NonAscii }.yes().test("").unwrap());
+        assert!(!Select::Text { mode: TextSelectMode::NonAscii }.yes().test("\n").unwrap());
+        assert!(Select::Text { mode: TextSelectMode::NonAscii }.yes().test("你好").unwrap());
         // not
-        assert!(!Select::Text { mode: TextSelectMode::Ascii }.not().test("abc"));
-        assert!(!Select::Text { mode: TextSelectMode::Ascii }.not().test(""));
-        assert!(!Select::Text { mode: TextSelectMode::Ascii }.not().test("\n"));
-        assert!(Select::Text { mode: TextSelectMode::Ascii }.not().test("你好"));
-        assert!(Select::Text { mode: TextSelectMode::NonAscii }.not().test("abc"));
-        assert!(!Select::Text { mode: TextSelectMode::NonAscii }.not().test(""));
-        assert!(Select::Text { mode: TextSelectMode::NonAscii }.not().test("\n"));
-        assert!(!Select::Text { mode: TextSelectMode::NonAscii }.not().test("你好"));
+        assert!(!Select::Text { mode: TextSelectMode::Ascii }.not().test("abc").unwrap());
+        assert!(!Select::Text { mode: TextSelectMode::Ascii }.not().test("").unwrap());
+        assert!(!Select::Text { mode: TextSelectMode::Ascii }.not().test("\n").unwrap());
+        assert!(Select::Text { mode: TextSelectMode::Ascii }.not().test("你好").unwrap());
+        assert!(Select::Text { mode: TextSelectMode::NonAscii }.not().test("abc").unwrap());
+        assert!(!Select::Text { mode: TextSelectMode::NonAscii }.not().test("").unwrap());
+        assert!(Select::Text { mode: TextSelectMode::NonAscii }.not().test("\n").unwrap());
+        assert!(!Select::Text { mode: TextSelectMode::NonAscii }.not().test("你好").unwrap());
     }
 
     #[test]
     fn test_text_empty_or_blank() {
         // empty
-        assert!(Select::Text { mode: TextSelectMode::Empty }.yes().test(""));
-        assert!(!Select::Text { mode: TextSelectMode::Empty }.yes().test("abc"));
-        assert!(!Select::Text { mode: TextSelectMode::Empty }.yes().test(" "));
-        assert!(!Select::Text { mode: TextSelectMode::Empty }.yes().test(" \n\t\r "));
-        assert!(!Select::Text { mode: TextSelectMode::Empty }.not().test(""));
-        assert!(Select::Text { mode: TextSelectMode::Empty }.not().test("abc"));
-        assert!(Select::Text { mode: TextSelectMode::Empty }.not().test(" "));
-        assert!(Select::Text { mode: TextSelectMode::Empty }.not().test(" \n\t\r "));
+        assert!(Select::Text { mode: TextSelectMode::Empty }.yes().test("").unwrap());
+        assert!(!Select::Text { mode: TextSelectMode::Empty }.yes().test("abc").unwrap());
+        assert!(!Select::Text { mode: TextSelectMode::Empty }.yes().test(" ").unwrap());
+        assert!(!Select::Text { mode: TextSelectMode::Empty }.yes().test(" \n\t\r ").unwrap());
+        assert!(!Select::Text { mode: TextSelectMode::Empty }.not().test("").unwrap());
+        assert!(Select::Text { mode: TextSelectMode::Empty }.not().test("abc").unwrap());
+        assert!(Select::Text { mode: TextSelectMode::Empty }.not().test(" ").unwrap());
+        assert!(Select::Text { mode: TextSelectMode::Empty }.not().test(" \n\t\r ").unwrap());
         // blank
-        assert!(Select::Text { mode: TextSelectMode::Blank }.yes().test(""));
-        assert!(!Select::Text { mode: TextSelectMode::Blank }.yes().test("abc"));
-        assert!(Select::Text { mode: TextSelectMode::Blank }.yes().test(" "));
-        assert!(Select::Text { mode: TextSelectMode::Blank }.yes().test(" \n\t\r "));
-        assert!(!Select::Text { mode: TextSelectMode::Blank }.not().test(""));
-        assert!(Select::Text { mode: TextSelectMode::Blank }.not().test("abc"));
-        assert!(!Select::Text { mode: TextSelectMode::Blank }.not().test(" "));
-        assert!(!Select::Text { mode: TextSelectMode::Blank }.not().test(" \n\t\r "));
+        assert!(Select::Text { mode: TextSelectMode::Blank }.yes().test("").unwrap());
+        assert!(!Select::Text { mode: TextSelectMode::Blank }.yes().test("abc").unwrap());
+        assert!(Select::Text { mode: TextSelectMode::Blank }.yes().test(" ").unwrap());
+        assert!(Select::Text { mode: TextSelectMode::Blank }.yes().test(" \n\t\r ").unwrap());
+        assert!(!Select::Text { mode: TextSelectMode::Blank }.not().test("").unwrap());
+        assert!(Select::Text { mode: TextSelectMode::Blank }.not().test("abc").unwrap());
+        assert!(!Select::Text { mode: TextSelectMode::Blank }.not().test(" ").unwrap());
+        assert!(!Select::Text { mode: TextSelectMode::Blank }.not().test(" \n\t\r ").unwrap());
     }
 
     #[test]
     fn test_reg_match() {
         assert!(Select::new_reg_match(r"[").is_err());
         // yes
-        assert!(Select::new_reg_match(r"\d+").unwrap().yes().test("123"));
-        assert!(!Select::new_reg_match(r"\d+").unwrap().yes().test("123abc"));
-        assert!(!Select::new_reg_match(r"\d+").unwrap().yes().test("123\n123"));
-        assert!(!Select::new_reg_match(r"(?m)\d+").unwrap().yes().test("123\n123"));
-        assert!(Select::new_reg_match(r"(?m)[\d\n]+").unwrap().yes().test("123\n123"));
+        assert!(Select::new_reg_match(r"\d+").unwrap().yes().test("123").unwrap());
+        assert!(!Select::new_reg_match(r"\d+").unwrap().yes().test("123abc").unwrap());
+        assert!(!Select::new_reg_match(r"\d+").unwrap().yes().test("123\n123").unwrap());
+        assert!(!Select::new_reg_match(r"(?m)\d+").unwrap().yes().test("123\n123").unwrap());
+        assert!(Select::new_reg_match(r"(?m)[\d\n]+").unwrap().yes().test("123\n123").unwrap());
         // not
-        assert!(!Select::new_reg_match(r"\d+").unwrap().not().test("123"));
-        assert!(Select::new_reg_match(r"\d+").unwrap().not().test("123abc"));
-        assert!(Select::new_reg_match(r"\d+").unwrap().not().test("123\n123"));
-        assert!(Select::new_reg_match(r"(?m)\d+").unwrap().not().test("123\n123"));
-        assert!(!Select::new_reg_match(r"(?m)[\d\n]+").unwrap().not().test("123\n123"));
+        assert!(!Select::new_reg_match(r"\d+").unwrap().not().test("123").unwrap());
+        assert!(Select::new_reg_match(r"\d+").unwrap().not().test("123abc").unwrap());
+        assert!(Select::new_reg_match(r"\d+").unwrap().not().test("123\n123").unwrap());
+        assert!(Select::new_reg_match(r"(?m)\d+").unwrap().not().test("123\n123").unwrap());
+        assert!(!Select::new_reg_match(r"(?m)[\d\n]+").unwrap().not().test("123\n123").unwrap());
     }
 }
@@ -1,3 +1,4 @@
+use crate::encoding::{RecordSep, TextEncoding};
 use crate::err::RpErr;
 use crate::input::Input;
 use crate::op::Op;
@@ -31,12 +32,51 @@ pub(crate) enum Config {
     Nocase,
     /// -s,--skip-err 全局忽略错误。
     SkipErr,
+    /// -e,--encoding   指定读取输入和写入输出时使用的文本编码。
+    ///                 -e|--encoding <encoding>
+    ///                     <encoding>  文本编码，必选：
+    ///                         utf8            严格UTF-8，遇到无效数据时报错，默认编码。
+    ///                         lossy           UTF-8，无效数据替换为'U+FFFD'。
+    ///                         latin1          ISO-8859-1，写入时无法编码的字符替换为'?'。
+    ///                         utf16le         UTF-16LE，读取时如果存在BOM则按照BOM识别。
+    ///                         utf16be         UTF-16BE，读取时如果存在BOM则按照BOM识别。
+    ///                         gbk             GBK，读取时无效数据替换为'U+FFFD'。
+    ///                 例如：
+    ///                     -e gbk
+    ///                     --encoding utf16le
+    Encoding(TextEncoding),
+    /// -r,--record-sep 指定读取输入和写入输出时使用的记录分隔符。
+    ///                 -r|--record-sep <sep>
+    ///                     <sep>       记录分隔符，必选：
+    ///                         lf              换行符，读取时同时识别'LF'和'CRLF'，默认分隔符。
+    ///                         nul             NUL字符，例如用于处理`find -print0`的输出。
+    ///                         para            段落模式，读取时以一个或多个空行分隔记录，
+    ///                                         记录内的多行以'LF'连接，写入时以一个空行分隔记录。
+    ///                         <string>        其他任意非空字符串。
+    ///                 输出命令中单独指定的换行符优先于此选项，段落模式下以此换行符组成分隔段落的空行。
+    ///                 例如：
+    ///                     -r nul
+    ///                     -r para
+    ///                     --record-sep ';'
+    RecordSep(RecordSep),
+    /// -i,--in-place   原地编辑文件，对每个文件分别执行流水线，并将结果写回原文件。
+    ///                 输入必须为不跟随的`:file`，输出必须为默认的标准输出。
+    ///                 结果先写入同目录下的临时文件，完成后再替换原文件，符号链接替换其指向的文件，
+    ///                 无法创建临时文件时先读取全部结果，再直接覆盖原文件。
+    ///                 写回时的压缩格式与读取时相同，即输入中指定的压缩格式，或者根据文件开头的魔数识别。
+    ///                 -i|--in-place[ <suffix>]
+    ///                     <suffix>    备份原文件时追加的后缀，不能以'-'或':'开头，
+    ///                                 可选，未指定则不备份。
+    ///                 例如：
+    ///                     -i :file config.toml :replace foo bar
+    ///                     --in-place .bak :file conf/ :trim
+    InPlace(Option<String>),
     /// -t,--token      以Token模式解析下一个参数。
     ///                 除了紧跟的第一个参数外，其他参数会被忽略。
     ///                 -t|--token <token>
     ///                     <token> 需要解析的文本参数，必选。
     ///                 例如：
-    ///                     -e ':in :uniq :to out'
+    ///                     -t ':in :uniq :to out'
     Token,
 }
 
@@ -50,6 +90,34 @@ pub(crate) fn skip_err(configs: &[Config]) -> bool {
     configs.contains(&Config::SkipErr)
 }
 
+#[inline]
+pub(crate) fn encoding(configs: &[Config]) -> TextEncoding {
+    configs
+        .iter()
+        .rev()
+        .find_map(|config| if let Config::Encoding(encoding) = config { Some(*encoding) } else { None })
+        .unwrap_or_default()
+}
+
+#[inline]
+pub(crate) fn record_sep(configs: &[Config]) -> &RecordSep {
+    static DEFAULT: RecordSep = RecordSep::Line;
+    configs
+        .iter()
+        .rev()
+        .find_map(|config| if let Config::RecordSep(record_sep) = config { Some(record_sep) } else { None })
+        .unwrap_or(&DEFAULT)
+}
+
+/// 原地编辑配置，未配置时返回`None`，否则返回可选的备份后缀。
+#[inline]
+pub(crate) fn in_place(configs: &[Config]) -> Option<Option<&str>> {
+    configs
+        .iter()
+        .rev()
+        .find_map(|config| if let Config::InPlace(backup) = config { Some(backup.as_deref()) } else { None })
+}
+
 pub(crate) fn print_pipe_info(configs: &[Config], input: &Input, ops: &Vec<Op>, output: &Output) {
     // TODO 2026-01-20 22:58 使用Display以可读形式描述流水线。
     println_info!("Configs:");
@@ -0,0 +1,340 @@
+use crate::err::RpErr;
+use encoding_rs_io::DecodeReaderBytesBuilder;
+use std::borrow::Cow;
+use std::io;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::str::FromStr;
+
+/// 输入输出的文本编码
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub(crate) enum TextEncoding {
+    /// 严格UTF-8，遇到无效数据时报错。
+    #[default]
+    Utf8,
+    /// UTF-8，无效数据替换为`U+FFFD`。
+    Lossy,
+    /// ISO-8859-1，每个字节对应一个字符。
+    Latin1,
+    /// UTF-16LE，读取时如果存在BOM则按照BOM识别。
+    Utf16Le,
+    /// UTF-16BE，读取时如果存在BOM则按照BOM识别。
+    Utf16Be,
+    /// GBK，读取时无效数据替换为`U+FFFD`。
+    Gbk,
+}
+
+impl FromStr for TextEncoding {
+    type Err = RpErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "utf8" | "utf-8" => Ok(TextEncoding::Utf8),
+            "lossy" => Ok(TextEncoding::Lossy),
+            "latin1" | "iso-8859-1" => Ok(TextEncoding::Latin1),
+            "utf16le" | "utf-16le" => Ok(TextEncoding::Utf16Le),
+            "utf16be" | "utf-16be" => Ok(TextEncoding::Utf16Be),
+            "gbk" => Ok(TextEncoding::Gbk),
+            _ => Err(RpErr::ArgParseErr {
+                cmd: "--encoding",
+                arg: "<encoding>",
+                arg_value: s.to_string(),
+                error: "unsupported encoding".to_string(),
+            }),
+        }
+    }
+}
+
+impl TextEncoding {
+    /// 按照当前编码和记录分隔符逐个读取记录，记录结尾的分隔符会被移除。
+    pub(crate) fn read_records(self, reader: impl Read + 'static, sep: &RecordSep) -> RecordReader {
+        let reader: Box<dyn BufRead> = match self {
+            TextEncoding::Utf8 | TextEncoding::Lossy | TextEncoding::Latin1 => Box::new(BufReader::new(reader)),
+            TextEncoding::Utf16Le => Box::new(BufReader::new(Self::transcode(reader, encoding_rs::UTF_16LE))),
+            TextEncoding::Utf16Be => Box::new(BufReader::new(Self::transcode(reader, encoding_rs::UTF_16BE))),
+            TextEncoding::Gbk => Box::new(BufReader::new(Self::transcode(reader, encoding_rs::GBK))),
+        };
+        let delimiter = match sep {
+            RecordSep::Line | RecordSep::Para => vec![b'\n'],
+            RecordSep::Nul => vec![0],
+            RecordSep::Str(sep) if self == TextEncoding::Latin1 => self.encode(sep).into_owned(),
+            RecordSep::Str(sep) => sep.as_bytes().to_vec(), // 其他编码均已转码为UTF-8
+        };
+        RecordReader { reader, encoding: self, sep: sep.clone(), delimiter, first: true, done: false }
+    }
+
+    /// 转码为UTF-8，同时识别并移除BOM。
+    fn transcode(reader: impl Read, encoding: &'static encoding_rs::Encoding) -> impl Read {
+        DecodeReaderBytesBuilder::new().encoding(Some(encoding)).bom_override(true).build(reader)
+    }
+
+    fn decode_line(self, line: Vec<u8>, first: bool) -> io::Result<String> {
+        let mut line = match self {
+            TextEncoding::Lossy => String::from_utf8_lossy(&line).into_owned(),
+            TextEncoding::Latin1 => line.into_iter().map(char::from).collect(),
+            _ => String::from_utf8(line).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?,
+        };
+        if first && line.starts_with('\u{feff}') {
+            line.remove(0);
+        }
+        Ok(line)
+    }
+
+    /// 按照当前编码编码文本，无法编码的字符会被替换。
+    pub(crate) fn encode<'a>(&self, text: &'a str) -> Cow<'a, [u8]> {
+        match self {
+            TextEncoding::Utf8 | TextEncoding::Lossy => Cow::Borrowed(text.as_bytes()),
+            TextEncoding::Latin1 => Cow::Owned(text.chars().map(|c| u8::try_from(c).unwrap_or(b'?')).collect()),
+            TextEncoding::Utf16Le => Cow::Owned(text.encode_utf16().flat_map(u16::to_le_bytes).collect()),
+            TextEncoding::Utf16Be => Cow::Owned(text.encode_utf16().flat_map(u16::to_be_bytes).collect()),
+            TextEncoding::Gbk => encoding_rs::GBK.encode(text).0,
+        }
+    }
+
+    /// 按照当前编码写入一个记录。
+    pub(crate) fn write_record(&self, writer: &mut impl Write, item: &str, postfix: &str) -> io::Result<()> {
+        writer.write_all(&self.encode(item))?;
+        writer.write_all(&self.encode(postfix))
+    }
+}
+
+/// 记录分隔符
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub(crate) enum RecordSep {
+    /// 换行符，读取时同时识别`\n`和`\r\n`。
+    #[default]
+    Line,
+    /// NUL字符`\0`。
+    Nul,
+    /// 段落模式，读取时以一个或多个空行分隔，写入时以一个空行分隔。
+    Para,
+    /// 任意非空字符串。
+    Str(String),
+}
+
+impl FromStr for RecordSep {
+    type Err = RpErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "" => Err(RpErr::ArgParseErr {
+                cmd: "--record-sep",
+                arg: "<sep>",
+                arg_value: s.to_string(),
+                error: "empty separator".to_string(),
+            }),
+            "lf" => Ok(RecordSep::Line),
+            "nul" => Ok(RecordSep::Nul),
+            "para" => Ok(RecordSep::Para),
+            _ => Ok(RecordSep::Str(s.to_string())),
+        }
+    }
+}
+
+impl RecordSep {
+    /// 写入时每个记录之后的分隔符，`crlf`为输出单独指定的换行符，指定时优先使用，
+    /// 按段落分隔时保留段落之间的空行。
+    pub(crate) fn postfix(&self, crlf: Option<bool>) -> &str {
+        match crlf {
+            Some(true) if *self == RecordSep::Para => "\r\n\r\n",
+            Some(false) if *self == RecordSep::Para => "\n\n",
+            Some(true) => "\r\n",
+            Some(false) => "\n",
+            None => match self {
+                RecordSep::Line => "\n",
+                RecordSep::Nul => "\0",
+                RecordSep::Para => "\n\n",
+                RecordSep::Str(sep) => sep,
+            },
+        }
+    }
+}
+
+/// 按记录读取并解码的迭代器，解码失败的记录返回错误但不中断读取，读取失败后则停止。
+pub(crate) struct RecordReader {
+    reader: Box<dyn BufRead>,
+    encoding: TextEncoding,
+    sep: RecordSep,
+    /// 分隔符编码后的字节，段落模式下为换行符
+    delimiter: Vec<u8>,
+    first: bool,
+    done: bool,
+}
+
+impl RecordReader {
+    /// 读取一个记录的原始字节，不包含分隔符，最后一个记录可以没有分隔符。
+    fn read_record(&mut self) -> Option<io::Result<Vec<u8>>> {
+        if self.done {
+            return None;
+        }
+        let last = *self.delimiter.last()?;
+        let mut buf = Vec::new();
+        loop {
+            match self.reader.read_until(last, &mut buf) {
+                Ok(0) => {
+                    self.done = true;
+                    break;
+                }
+                Ok(_) if buf.ends_with(&self.delimiter) => {
+                    buf.truncate(buf.len() - self.delimiter.len());
+                    if matches!(self.sep, RecordSep::Line | RecordSep::Para) && buf.ends_with(b"\r") {
+                        buf.pop();
+                    }
+                    return Some(Ok(buf));
+                }
+                Ok(_) => {} // 多字节分隔符仅匹配到最后一个字节，继续读取
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+        if buf.is_empty() { None } else { Some(Ok(buf)) }
+    }
+
+    /// 读取一个段落的原始字节，段落内的多行以`\n`连接，跳过连续的空行。
+    fn read_paragraph(&mut self) -> Option<io::Result<Vec<u8>>> {
+        let mut para = Vec::new();
+        while let Some(line) = self.read_record() {
+            match line {
+                Ok(line) if line.is_empty() => {
+                    if !para.is_empty() {
+                        return Some(Ok(para));
+                    }
+                }
+                Ok(line) => {
+                    if !para.is_empty() {
+                        para.push(b'\n');
+                    }
+                    para.extend(line);
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        }
+        if para.is_empty() { None } else { Some(Ok(para)) }
+    }
+}
+
+impl Iterator for RecordReader {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = if self.sep == RecordSep::Para { self.read_paragraph() } else { self.read_record() }?;
+        let first = std::mem::replace(&mut self.first, false);
+        Some(record.and_then(|record| self.encoding.decode_line(record, first)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn read_all(encoding: TextEncoding, bytes: &[u8]) -> Vec<Result<String, io::ErrorKind>> {
+        read_sep(encoding, &RecordSep::Line, bytes)
+    }
+
+    fn read_sep(encoding: TextEncoding, sep: &RecordSep, bytes: &[u8]) -> Vec<Result<String, io::ErrorKind>> {
+        encoding.read_records(Cursor::new(bytes.to_vec()), sep).map(|r| r.map_err(|err| err.kind())).collect()
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("utf8".parse::<TextEncoding>(), Ok(TextEncoding::Utf8));
+        assert_eq!("UTF-8".parse::<TextEncoding>(), Ok(TextEncoding::Utf8));
+        assert_eq!("lossy".parse::<TextEncoding>(), Ok(TextEncoding::Lossy));
+        assert_eq!("latin1".parse::<TextEncoding>(), Ok(TextEncoding::Latin1));
+        assert_eq!("utf16le".parse::<TextEncoding>(), Ok(TextEncoding::Utf16Le));
+        assert_eq!("utf16be".parse::<TextEncoding>(), Ok(TextEncoding::Utf16Be));
+        assert_eq!("GBK".parse::<TextEncoding>(), Ok(TextEncoding::Gbk));
+        assert!("ascii".parse::<TextEncoding>().is_err());
+    }
+
+    #[test]
+    fn test_read_utf8() {
+        assert_eq!(
+            read_all(TextEncoding::Utf8, b"\xef\xbb\xbfa\r\nb\n\xffc\nd"),
+            vec![Ok("a".to_string()), Ok("b".to_string()), Err(io::ErrorKind::InvalidData), Ok("d".to_string())]
+        );
+        assert_eq!(
+            read_all(TextEncoding::Lossy, b"a\n\xffc\n"),
+            vec![Ok("a".to_string()), Ok("\u{fffd}c".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_read_latin1() {
+        assert_eq!(read_all(TextEncoding::Latin1, b"caf\xe9\n\xff"), vec![Ok("café".to_string()), Ok("ÿ".to_string())]);
+    }
+
+    #[test]
+    fn test_read_utf16() {
+        let le = TextEncoding::Utf16Le.encode("中\r\nb\n").into_owned();
+        assert_eq!(read_all(TextEncoding::Utf16Le, &le), vec![Ok("中".to_string()), Ok("b".to_string())]);
+        let mut be = vec![0xfe, 0xff];
+        be.extend_from_slice(&TextEncoding::Utf16Be.encode("中\nb"));
+        assert_eq!(read_all(TextEncoding::Utf16Be, &be), vec![Ok("中".to_string()), Ok("b".to_string())]);
+        // BOM优先于指定的字节序
+        assert_eq!(read_all(TextEncoding::Utf16Le, &be), vec![Ok("中".to_string()), Ok("b".to_string())]);
+    }
+
+    #[test]
+    fn test_read_gbk() {
+        assert_eq!(
+            read_all(TextEncoding::Gbk, b"\xd6\xd0\xce\xc4\nabc"),
+            vec![Ok("中文".to_string()), Ok("abc".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(TextEncoding::Utf8.encode("中"), "中".as_bytes());
+        assert_eq!(TextEncoding::Latin1.encode("é中"), &b"\xe9?"[..]);
+        assert_eq!(TextEncoding::Utf16Le.encode("a中"), &[0x61, 0x00, 0x2d, 0x4e][..]);
+        assert_eq!(TextEncoding::Utf16Be.encode("a中"), &[0x00, 0x61, 0x4e, 0x2d][..]);
+        assert_eq!(TextEncoding::Gbk.encode("中文"), &b"\xd6\xd0\xce\xc4"[..]);
+    }
+
+    #[test]
+    fn test_read_records() {
+        assert_eq!(
+            read_sep(TextEncoding::Utf8, &RecordSep::Nul, b"a\nb\0c\0"),
+            vec![Ok("a\nb".to_string()), Ok("c".to_string())]
+        );
+        assert_eq!(
+            read_sep(TextEncoding::Utf8, &RecordSep::Str("--".to_string()), b"a-b--c-\n--d"),
+            vec![Ok("a-b".to_string()), Ok("c-\n".to_string()), Ok("d".to_string())]
+        );
+        assert_eq!(
+            read_sep(TextEncoding::Latin1, &RecordSep::Str("é".to_string()), b"a\xe9b"),
+            vec![Ok("a".to_string()), Ok("b".to_string())]
+        );
+        let le = TextEncoding::Utf16Le.encode("a\0b").into_owned();
+        assert_eq!(
+            read_sep(TextEncoding::Utf16Le, &RecordSep::Nul, &le),
+            vec![Ok("a".to_string()), Ok("b".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_read_paragraph() {
+        assert_eq!(
+            read_sep(TextEncoding::Utf8, &RecordSep::Para, b"\n\nerr1\r\n  at a\n\n\n\nerr2\n  at b\n  at c\n"),
+            vec![Ok("err1\n  at a".to_string()), Ok("err2\n  at b\n  at c".to_string())]
+        );
+        assert_eq!(read_sep(TextEncoding::Utf8, &RecordSep::Para, b"\n\n"), vec![]);
+    }
+
+    #[test]
+    fn test_record_sep() {
+        assert_eq!("NUL".parse::<RecordSep>(), Ok(RecordSep::Nul));
+        assert_eq!("para".parse::<RecordSep>(), Ok(RecordSep::Para));
+        assert_eq!("lf".parse::<RecordSep>(), Ok(RecordSep::Line));
+        assert_eq!(";".parse::<RecordSep>(), Ok(RecordSep::Str(";".to_string())));
+        assert!("".parse::<RecordSep>().is_err());
+        assert_eq!(RecordSep::Para.postfix(None), "\n\n");
+        assert_eq!(RecordSep::Nul.postfix(Some(true)), "\r\n");
+        assert_eq!(RecordSep::Para.postfix(Some(true)), "\r\n\r\n");
+        assert_eq!(RecordSep::Para.postfix(Some(false)), "\n\n");
+        assert_eq!(RecordSep::Str(";".to_string()).postfix(None), ";");
+    }
+}
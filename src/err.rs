@@ -61,6 +61,34 @@ pub enum RpErr {
         "[InvalidNonNegativeIntArg:14] Positive integer or zero is required by argument `{arg}` of cmd `{cmd}`, but it is {arg_value:?}"
     )]
     InvalidNonNegativeIntArg { cmd: &'static str, arg: &'static str, arg_value: String },
+
+    /// 15      从标准输入读取数据失败。
+    #[error("[ReadFromStdInErr:15] Read line {line_no} of stdin error: {err}")]
+    ReadFromStdInErr { line_no: usize, err: String },
+
+    /// 16      写入数据到标准输出失败。
+    #[error("[WriteToStdOutErr:16] Write item {item:?} to stdout error: {err}")]
+    WriteToStdOutErr { item: String, err: String },
+
+    /// 17      运行外部命令失败。
+    #[error("[RunCmdErr:17] Run command {cmd:?} error: {err}")]
+    RunCmdErr { cmd: String, err: String },
+
+    /// 18      外部命令退出状态失败。
+    #[error("[CmdExitErr:18] Command {cmd:?} failed with {status}")]
+    CmdExitErr { cmd: String, status: String },
+
+    /// 19      解析JSON数据失败。
+    #[error("[ParseJsonErr:19] Parse json from {item:?} error: {err}")]
+    ParseJsonErr { item: String, err: String },
+
+    /// 20      拆分输出时数据的键不能作为文件名。
+    #[error("[InvalidSplitKeyErr:20] Key {key:?} of item {item:?} is not a valid file name")]
+    InvalidSplitKeyErr { key: String, item: String },
+
+    /// 1-255   输出命令退出状态失败，退出码与输出命令的退出码相同。
+    #[error("[OutputCmdExitErr:{code}] Output command {cmd:?} exited with code {code}")]
+    OutputCmdExitErr { cmd: String, code: i32 },
 }
 
 impl Termination for RpErr {
@@ -72,6 +100,7 @@ impl Termination for RpErr {
 
 impl RpErr {
     pub fn termination(self) -> ! {
+        crate::file::remove_temp_files();
         let exit_code = self.exit_code();
         self.report();
         std::process::exit(exit_code as i32);
@@ -93,6 +122,13 @@ impl RpErr {
             RpErr::ParseRegexErr { .. } => 12,
             RpErr::ParseNumErr { .. } => 13,
             RpErr::InvalidNonNegativeIntArg { .. } => 14,
+            RpErr::ReadFromStdInErr { .. } => 15,
+            RpErr::WriteToStdOutErr { .. } => 16,
+            RpErr::RunCmdErr { .. } => 17,
+            RpErr::CmdExitErr { .. } => 18,
+            RpErr::ParseJsonErr { .. } => 19,
+            RpErr::InvalidSplitKeyErr { .. } => 20,
+            RpErr::OutputCmdExitErr { code, .. } => u8::try_from(*code).unwrap_or(u8::MAX),
         }
     }
 }
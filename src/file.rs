@@ -0,0 +1,408 @@
+use crate::compress::Compress;
+use crate::config::{skip_err, Config};
+use crate::err::RpErr;
+use glob::{MatchOptions, Pattern};
+use std::fs::{File, Metadata};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf, MAIN_SEPARATOR};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use std::{fs, io, thread};
+
+const MATCH_OPTIONS: MatchOptions =
+    MatchOptions { case_sensitive: true, require_literal_separator: true, require_literal_leading_dot: false };
+
+/// 文件输入参数
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct FileArg {
+    /// 文件路径、目录路径或通配符
+    pub(crate) files: Vec<String>,
+    /// 是否递归读取目录
+    pub(crate) recursive: bool,
+    /// 目录和通配符展开后需要包含的文件，为空时包含全部
+    pub(crate) include: Vec<Pattern>,
+    /// 目录和通配符展开后需要排除的文件
+    pub(crate) exclude: Vec<Pattern>,
+    /// 压缩格式，未指定时根据魔数识别
+    pub(crate) compress: Option<Compress>,
+    /// 是否在读取到文件结尾后持续跟随新写入的数据
+    pub(crate) follow: bool,
+}
+
+impl FileArg {
+    pub(crate) fn new(files: Vec<String>) -> FileArg {
+        FileArg { files, recursive: false, include: Vec::new(), exclude: Vec::new(), compress: None, follow: false }
+    }
+
+    /// 使用相同的选项读取另一组文件。
+    pub(crate) fn with_files(&self, files: Vec<String>) -> FileArg {
+        FileArg { files, ..self.clone() }
+    }
+
+    /// 打开文件，需要跟随时不解压，否则按照压缩格式解压。
+    pub(crate) fn open(&self, file: &str, follow: bool) -> io::Result<Box<dyn Read>> {
+        let fin = File::open(file)?;
+        if follow { Ok(Box::new(FollowReader::new(file, fin))) } else { Compress::decoder(self.compress, fin) }
+    }
+
+    /// 展开所有的目录和通配符，得到需要读取的文件列表。
+    /// 每个参数展开的结果按照路径排序，参数之间保持指定的顺序。
+    pub(crate) fn paths(&self, configs: &[Config]) -> Result<Vec<String>, RpErr> {
+        let mut res = Vec::new();
+        for file in &self.files {
+            let path = Path::new(file);
+            if path.is_dir() {
+                let mut paths = Vec::new();
+                self.walk_dir(path, &mut paths, configs)?;
+                self.extend_filtered(&mut res, paths);
+            } else if path.exists() || !is_glob(file) {
+                res.push(file.clone()); // 直接指定的文件不过滤，不存在时在打开文件时报错
+            } else {
+                let entries = glob::glob_with(file, MATCH_OPTIONS).map_err(|err| RpErr::ArgParseErr {
+                    cmd: ":file",
+                    arg: "<file>",
+                    arg_value: file.clone(),
+                    error: err.to_string(),
+                })?;
+                let mut paths = Vec::new();
+                for entry in entries {
+                    match entry {
+                        Ok(path) if path.is_file() => paths.push(path),
+                        Ok(_) => {} // 忽略目录
+                        Err(err) => skip_or_err(err.path(), err.error().to_string(), configs)?,
+                    }
+                }
+                self.extend_filtered(&mut res, paths);
+            }
+        }
+        Ok(res)
+    }
+
+    fn walk_dir(&self, dir: &Path, paths: &mut Vec<PathBuf>, configs: &[Config]) -> Result<(), RpErr> {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(err) => return skip_or_err(dir, err.to_string(), configs),
+        };
+        for entry in entries {
+            match entry {
+                Ok(entry) => {
+                    let path = entry.path();
+                    if path.is_file() {
+                        paths.push(path);
+                    } else if self.recursive && entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                        self.walk_dir(&path, paths, configs)?; // 不跟随目录的符号链接，避免循环
+                    }
+                }
+                Err(err) => skip_or_err(dir, err.to_string(), configs)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn extend_filtered(&self, res: &mut Vec<String>, mut paths: Vec<PathBuf>) {
+        paths.sort();
+        res.extend(
+            paths
+                .into_iter()
+                .filter(|path| self.include.is_empty() || self.include.iter().any(|pattern| matches(pattern, path)))
+                .filter(|path| !self.exclude.iter().any(|pattern| matches(pattern, path)))
+                .map(|path| path.to_string_lossy().into_owned()),
+        );
+    }
+}
+
+/// 跟随文件的读取器，读取到文件结尾后等待新数据写入而不是结束，因此永远不会返回`Ok(0)`。
+/// 文件被截断时从头读取；文件被重命名轮转时，读完旧文件后打开同路径的新文件继续读取。
+struct FollowReader {
+    path: PathBuf,
+    file: File,
+    pos: u64,
+}
+
+impl FollowReader {
+    const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+    fn new(path: &str, file: File) -> FollowReader {
+        FollowReader { path: PathBuf::from(path), file, pos: 0 }
+    }
+
+    /// 路径是否已经指向其他文件，路径暂时不存在时视为未轮转。
+    fn rotated(&self) -> io::Result<bool> {
+        match fs::metadata(&self.path) {
+            Ok(meta) => Ok(!same_file(&meta, &self.file.metadata()?)),
+            Err(_) => Ok(false),
+        }
+    }
+}
+
+impl Read for FollowReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let n = self.file.read(buf)?;
+            if n > 0 || buf.is_empty() {
+                self.pos += n as u64;
+                return Ok(n);
+            }
+            if self.rotated()? {
+                self.file = File::open(&self.path)?;
+                self.pos = 0;
+            } else if self.file.metadata()?.len() < self.pos {
+                self.pos = self.file.seek(SeekFrom::Start(0))?;
+            } else {
+                thread::sleep(Self::POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+/// 原子写入的文件：先写入同一目录下的临时文件，提交时重命名为目标文件。
+/// 目标文件为符号链接时写入链接指向的文件，保留符号链接本身。
+/// 未提交时丢弃会删除临时文件；进程因错误终止时，通过`remove_temp_files`删除临时文件。
+pub(crate) struct AtomicFile {
+    path: PathBuf,
+    temp: PathBuf,
+    file: File,
+}
+
+static TEMP_FILES: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+static TEMP_ID: AtomicUsize = AtomicUsize::new(0);
+
+impl AtomicFile {
+    /// 在目标文件（解析符号链接后）所在目录创建临时文件，如果目标文件已经存在，则临时文件使用目标文件的权限和所有者。
+    /// 无法创建临时文件或者无法保留所有者时返回错误，调用方可以改为直接写入目标文件。
+    pub(crate) fn create(path: &str) -> io::Result<AtomicFile> {
+        let path = fs::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path));
+        let name = path.file_name().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not a file path"))?;
+        let temp = path.with_file_name(format!(
+            ".{}.rp-{}-{}.tmp",
+            name.to_string_lossy(),
+            std::process::id(),
+            TEMP_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        let file = File::create(&temp)?;
+        TEMP_FILES.lock().unwrap().push(temp.clone());
+        let atomic = AtomicFile { path, temp, file };
+        if let Ok(meta) = fs::metadata(&atomic.path) {
+            fs::set_permissions(&atomic.temp, meta.permissions())?;
+            set_owner(&atomic.file, &meta)?;
+        }
+        Ok(atomic)
+    }
+
+    /// 将临时文件重命名为目标文件，如果指定了备份后缀，则先将已经存在的目标文件复制为备份文件。
+    /// 无法重命名时（例如目录只读但是文件可写）将临时文件的内容直接复制到目标文件。
+    pub(crate) fn commit(&mut self, backup: Option<&str>) -> io::Result<()> {
+        self.file.sync_all()?;
+        if let Some(suffix) = backup
+            && self.path.exists()
+        {
+            let mut backup = self.path.clone().into_os_string();
+            backup.push(suffix);
+            fs::copy(&self.path, backup)?;
+        }
+        if fs::rename(&self.temp, &self.path).is_err() {
+            fs::copy(&self.temp, &self.path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn set_owner(file: &File, meta: &Metadata) -> io::Result<()> {
+    use std::os::unix::fs::{fchown, MetadataExt};
+    let temp = file.metadata()?;
+    if temp.uid() != meta.uid() || temp.gid() != meta.gid() {
+        fchown(file, Some(meta.uid()), Some(meta.gid()))?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_owner(_file: &File, _meta: &Metadata) -> io::Result<()> {
+    Ok(())
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.temp); // 提交后临时文件已经不存在
+        TEMP_FILES.lock().unwrap().retain(|temp| temp != &self.temp);
+    }
+}
+
+/// 删除所有未提交的临时文件。
+pub(crate) fn remove_temp_files() {
+    if let Ok(temps) = TEMP_FILES.lock() {
+        for temp in temps.iter() {
+            let _ = fs::remove_file(temp);
+        }
+    }
+}
+
+#[cfg(unix)]
+fn same_file(a: &Metadata, b: &Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    a.dev() == b.dev() && a.ino() == b.ino()
+}
+
+#[cfg(not(unix))]
+fn same_file(a: &Metadata, b: &Metadata) -> bool {
+    match (a.created(), b.created()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => true,
+    }
+}
+
+fn skip_or_err(path: &Path, err: String, configs: &[Config]) -> Result<(), RpErr> {
+    if skip_err(configs) { Ok(()) } else { Err(RpErr::OpenFileErr { file: path.to_string_lossy().into_owned(), err }) }
+}
+
+#[inline]
+fn is_glob(file: &str) -> bool {
+    file.contains(['*', '?', '['])
+}
+
+/// 包含路径分隔符的模式匹配整个路径，否则仅匹配文件名。
+fn matches(pattern: &Pattern, path: &Path) -> bool {
+    if pattern.as_str().contains(['/', MAIN_SEPARATOR]) {
+        pattern.matches_path_with(path, MATCH_OPTIONS)
+    } else {
+        path.file_name().is_some_and(|name| pattern.matches_with(&name.to_string_lossy(), MATCH_OPTIONS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{RecordSep, TextEncoding};
+
+    fn setup(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("rp_file_test_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for file in ["b.log", "a.log", "c.txt", "sub/d.log", "sub/deep/e.log", "sub/deep/f.txt"] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, file).unwrap();
+        }
+        root
+    }
+
+    fn relative(root: &Path, paths: Vec<String>) -> Vec<String> {
+        paths
+            .into_iter()
+            .map(|path| Path::new(&path).strip_prefix(root).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect()
+    }
+
+    #[test]
+    fn test_paths_dir() {
+        let root = setup("dir");
+        let dir = root.to_string_lossy().into_owned();
+        let arg = FileArg::new(vec![dir.clone()]);
+        assert_eq!(relative(&root, arg.paths(&[]).unwrap()), vec!["a.log", "b.log", "c.txt"]);
+        let arg = FileArg { recursive: true, ..FileArg::new(vec![dir.clone()]) };
+        assert_eq!(
+            relative(&root, arg.paths(&[]).unwrap()),
+            vec!["a.log", "b.log", "c.txt", "sub/d.log", "sub/deep/e.log", "sub/deep/f.txt"]
+        );
+        let arg = FileArg {
+            recursive: true,
+            include: vec![Pattern::new("*.log").unwrap()],
+            exclude: vec![Pattern::new("**/deep/*").unwrap(), Pattern::new("b*").unwrap()],
+            ..FileArg::new(vec![dir])
+        };
+        assert_eq!(relative(&root, arg.paths(&[]).unwrap()), vec!["a.log", "sub/d.log"]);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_paths_glob() {
+        let root = setup("glob");
+        let glob = |pattern: &str| format!("{}/{pattern}", root.to_string_lossy());
+        let arg = FileArg::new(vec![glob("*.log")]);
+        assert_eq!(relative(&root, arg.paths(&[]).unwrap()), vec!["a.log", "b.log"]);
+        let arg = FileArg::new(vec![glob("**/*.log"), glob("c.txt")]);
+        assert_eq!(
+            relative(&root, arg.paths(&[]).unwrap()),
+            vec!["a.log", "b.log", "sub/d.log", "sub/deep/e.log", "c.txt"]
+        );
+        let arg = FileArg { exclude: vec![Pattern::new("*.log").unwrap()], ..FileArg::new(vec![glob("**/*")]) };
+        assert_eq!(relative(&root, arg.paths(&[]).unwrap()), vec!["c.txt", "sub/deep/f.txt"]);
+        // 直接指定的文件不过滤，也不检查是否存在
+        let arg = FileArg { exclude: vec![Pattern::new("*").unwrap()], ..FileArg::new(vec![glob("not_exists.txt")]) };
+        assert_eq!(relative(&root, arg.paths(&[]).unwrap()), vec!["not_exists.txt"]);
+        assert!(FileArg::new(vec![glob("***")]).paths(&[]).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_follow() {
+        let root = setup("follow");
+        let path = root.join("follow.log");
+        let file = path.to_string_lossy().into_owned();
+        fs::write(&path, "a\n").unwrap();
+        let mut lines =
+            TextEncoding::Utf8.read_records(FileArg::new(vec![]).open(&file, true).unwrap(), &RecordSep::Line);
+        assert_eq!(lines.next().unwrap().unwrap(), "a");
+        // 追加
+        fs::OpenOptions::new().append(true).open(&path).unwrap().write_all(b"bb\n").unwrap();
+        assert_eq!(lines.next().unwrap().unwrap(), "bb");
+        // 截断
+        fs::write(&path, "c\n").unwrap();
+        assert_eq!(lines.next().unwrap().unwrap(), "c");
+        // 重命名轮转
+        fs::rename(&path, root.join("follow.log.1")).unwrap();
+        fs::write(&path, "d\n").unwrap();
+        assert_eq!(lines.next().unwrap().unwrap(), "d");
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_atomic_file() {
+        let root = setup("atomic");
+        let path = root.join("a.log");
+        let file = path.to_string_lossy().into_owned();
+        let mut atomic = AtomicFile::create(&file).unwrap();
+        atomic.write_all(b"new").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a.log"); // 提交之前不影响目标文件
+        atomic.commit(Some(".bak")).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(fs::read_to_string(root.join("a.log.bak")).unwrap(), "a.log");
+        let mut atomic = AtomicFile::create(&file).unwrap();
+        atomic.write_all(b"dropped").unwrap();
+        drop(atomic); // 未提交时丢弃
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        let mut names = fs::read_dir(&root).unwrap().map(|e| e.unwrap().file_name()).collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["a.log", "a.log.bak", "b.log", "c.txt", "sub"]);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_atomic_file_symlink() {
+        let root = setup("atomic_symlink");
+        let link = root.join("link.log");
+        std::os::unix::fs::symlink(root.join("sub/d.log"), &link).unwrap();
+        let mut atomic = AtomicFile::create(&link.to_string_lossy()).unwrap();
+        atomic.write_all(b"new").unwrap();
+        atomic.commit(None).unwrap();
+        // 写入链接指向的文件，符号链接保持不变
+        assert!(fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_to_string(root.join("sub/d.log")).unwrap(), "new");
+        let mut names = fs::read_dir(root.join("sub")).unwrap().map(|e| e.unwrap().file_name()).collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["d.log", "deep"]);
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Float, Integer};
+use crate::{Float, Integer, Num};
 use rt_format::{Format, FormatArgument, NoPositionalArguments, Specifier};
 use std::fmt::Formatter;
 
@@ -39,6 +39,15 @@ impl From<Float> for FmtArg {
     }
 }
 
+impl From<Num> for FmtArg {
+    fn from(value: Num) -> Self {
+        match value {
+            Num::Integer(integer) => FmtArg::Integer(integer),
+            Num::Float(float) => FmtArg::Float(float),
+        }
+    }
+}
+
 impl FormatArgument for FmtArg {
     fn supports_format(&self, specifier: &Specifier) -> bool {
         match self {
@@ -57,6 +66,8 @@ impl FormatArgument for FmtArg {
     fn fmt_display(&self, f: &mut Formatter) -> std::fmt::Result {
         match self {
             FmtArg::String(string) => std::fmt::Display::fmt(&string, f),
+            // 整数会忽略精度，指定精度时按照浮点数输出，例如`{n:08.3}`
+            FmtArg::Integer(integer) if f.precision().is_some() => std::fmt::Display::fmt(&(*integer as Float), f),
             FmtArg::Integer(integer) => std::fmt::Display::fmt(&integer, f),
             FmtArg::Float(float) => std::fmt::Display::fmt(&float, f),
         }
@@ -131,5 +142,11 @@ mod tests {
             Ok("{Jack}".to_string()),
             fmt_args("{{{name}}}", &vec![("name", FmtArg::from("Jack")), ("age", FmtArg::from(12))])
         );
+        assert_eq!(
+            Ok("0012|0001.500".to_string()),
+            fmt_args("{i:04}|{f:08.3}", &[("i", FmtArg::from(Num::from(12))), ("f", FmtArg::from(Num::from(1.5)))])
+        );
+        assert_eq!(Ok("0012.000|12".to_string()), fmt_args("{n:08.3}|{n}", &[("n", FmtArg::from(Num::from(12)))]));
+        assert!(fmt_args("{missing}", &[("name", FmtArg::from("Jack"))]).is_err());
     }
 }
@@ -1,4 +1,4 @@
-use crate::condition::Select;
+use crate::condition::{Condition, Select};
 use crate::config::Config;
 use crate::err::RpErr;
 use crate::input::Input;
@@ -81,6 +81,10 @@ fn print_fmt_help() {
 
 fn print_condition_help() {
     println!("\n条件表达式：");
+    for (_, help) in Condition::all_help() {
+        println!("{}", help);
+    }
+    println!("\n<select> 选择条件：");
     for (_, help) in Select::all_help() {
         println!("{}", help);
     }
@@ -1,13 +1,14 @@
-use crate::config::{skip_err, Config};
+use crate::cmd::CmdArg;
+use crate::config::{encoding, record_sep, skip_err, Config};
 use crate::err::RpErr;
+use crate::file::FileArg;
 use crate::fmt::{fmt_args, FmtArg};
 use crate::pipe::Pipe;
+use crate::source::{clear_source, set_source};
 use crate::{Integer, PipeRes};
 use cmd_help::CmdHelp;
-use std::fs::File;
 use std::io;
-use std::io::{BufRead, BufReader};
-use std::iter::repeat;
+use std::iter::{from_fn, repeat};
 use std::rc::Rc;
 
 #[derive(Debug, Eq, PartialEq, CmdHelp)]
@@ -15,13 +16,46 @@ pub(crate) enum Input {
     /// :in         从标准输入读取输入。
     ///             未指定元素输入时的默认输入。
     StdIn,
-    /// :file       从文件读取输入。
-    ///             :file <file>[ <file>][...]
-    ///                 <file>  文件路径，至少指定一个。
+    /// :file       从文件读取输入，支持目录、通配符和压缩文件。
+    ///             :file <file>[ <file>][...][ recursive][ include <glob>][ exclude <glob>][ gzip|zstd|bzip2|xz|plain][ follow]
+    ///                 <file>  文件路径、目录路径或通配符，至少指定一个。
+    ///                         目录读取其中的全部文件，通配符支持'*'、'?'、'[...]'和'**'。
+    ///                         每个参数展开后的文件按照路径排序，参数之间保持指定的顺序。
+    ///                         如果文件名与选项相同，需要添加路径前缀，例如'./gzip'。
+    ///                 recursive
+    ///                         递归读取目录中的文件，可选，不跟随目录的符号链接。
+    ///                 include <glob>
+    ///                         仅读取匹配的文件，可选，可以指定多次，匹配任意一个即可。
+    ///                 exclude <glob>
+    ///                         排除匹配的文件，可选，可以指定多次。
+    ///                         include和exclude仅作用于目录和通配符展开得到的文件，
+    ///                         如果<glob>包含路径分隔符则匹配整个路径，否则仅匹配文件名。
+    ///                 gzip|zstd|bzip2|xz|plain
+    ///                         指定所有文件的压缩格式，可选，plain表示不解压。
+    ///                         未指定时根据文件内容开头的魔数识别，无法识别则视为未压缩。
+    ///                 follow  读取到文件结尾后持续等待并读取新写入的数据，类似`tail -f`，可选。
+    ///                         文件被截断时从头读取，文件被重命名轮转时继续读取同路径的新文件。
+    ///                         指定多个文件时依次读取，仅跟随最后一个文件，跟随的文件不解压。
     ///             例如：
     ///                 :file input.txt
     ///                 :file input1.txt input2.txt input3.txt
-    File { files: Vec<String> },
+    ///                 :file app.log.1.gz app.log.2.zst
+    ///                 :file app.log.gz plain
+    ///                 :file 'logs/**/*.log'
+    ///                 :file logs recursive include '*.log' exclude '*.bak.log'
+    ///                 :file app.log follow
+    File(FileArg),
+    /// :cmd        运行外部命令，从其标准输出读取输入。
+    ///             :cmd <program>[ <arg>][...]
+    ///                 <program>   程序名称或路径，必选，名称从环境变量`PATH`中查找。
+    ///                 <arg>       程序参数，可选，如果以':'开头，需要使用'\:'转义。
+    ///             直接启动程序而不经过shell，因此不支持管道、重定向和通配符等shell语法。
+    ///             程序的标准错误直接输出，标准输入为空。
+    ///             程序退出状态失败时终止，如果全局配置了跳过错误，则忽略退出状态。
+    ///             例如：
+    ///                 :cmd git log --oneline
+    ///                 :cmd ls -l /tmp
+    Cmd(CmdArg),
     /// :clip       从剪切板读取输入。
     #[cfg(windows)]
     Clip,
@@ -68,8 +102,11 @@ impl Input {
     pub(crate) fn new_std_in() -> Input {
         Input::StdIn
     }
-    pub(crate) fn new_file(files: Vec<String>) -> Input {
-        Input::File { files }
+    pub(crate) fn new_file(file_arg: FileArg) -> Input {
+        Input::File(file_arg)
+    }
+    pub(crate) fn new_cmd(cmd_arg: CmdArg) -> Input {
+        Input::Cmd(cmd_arg)
     }
 
     #[cfg(windows)]
@@ -90,43 +127,118 @@ impl Input {
 impl Input {
     pub(crate) fn try_into(self, configs: &'static [Config]) -> PipeRes {
         match self {
-            Input::StdIn => Ok(Pipe {
-                iter: Box::new(
-                    io::stdin().lock().lines().into_iter().take_while(Result::is_ok).map(|line| line.unwrap()),
-                ),
-            }),
-            Input::File { files } => Ok(Pipe {
-                iter: Box::new(
-                    files
-                        .into_iter()
-                        .map(|f| (File::open(&f), f))
-                        .filter_map(|(r, f)| match r {
-                            Ok(fin) => Some((fin, f)),
-                            Err(err) => {
-                                if skip_err(configs) {
-                                    None
-                                } else {
-                                    RpErr::OpenFileErr { file: f, err: err.to_string() }.termination();
+            Input::StdIn => {
+                let std_in = Rc::new("-".to_string());
+                Ok(Pipe {
+                    iter: Box::new(
+                        encoding(configs)
+                            .read_records(io::stdin().lock(), record_sep(configs))
+                            .enumerate()
+                            .filter_map(move |(line, lr)| match lr {
+                                Ok(item) => {
+                                    set_source(&std_in, line + 1);
+                                    Some(item)
                                 }
-                            }
-                        })
-                        .map(|(fin, f)| (BufReader::new(fin), Rc::new(f)))
-                        .flat_map(|(reader, f)| {
-                            BufRead::lines(reader).into_iter().enumerate().map(move |l| (l, f.clone()))
-                        })
-                        .filter_map(|((line, lr), f)| match lr {
-                            Ok(line) => Some(line),
-                            Err(err) => {
-                                if skip_err(configs) {
-                                    None
-                                } else {
-                                    RpErr::ReadFromFileErr { file: (*f).clone(), line_no: line, err: err.to_string() }
+                                Err(err) => {
+                                    if skip_err(configs) {
+                                        None
+                                    } else {
+                                        RpErr::ReadFromStdInErr { line_no: line + 1, err: err.to_string() }
+                                            .termination();
+                                    }
+                                }
+                            })
+                            .chain(from_fn(|| {
+                                clear_source();
+                                None
+                            })),
+                    ),
+                })
+            }
+            Input::File(file_arg) => {
+                let paths = file_arg.paths(configs)?;
+                let last = paths.len().saturating_sub(1);
+                Ok(Pipe {
+                    iter: Box::new(
+                        paths
+                            .into_iter()
+                            .enumerate()
+                            .map(move |(idx, f)| (file_arg.open(&f, file_arg.follow && idx == last), f))
+                            .filter_map(|(r, f)| match r {
+                                Ok(fin) => Some((fin, f)),
+                                Err(err) => {
+                                    if skip_err(configs) {
+                                        None
+                                    } else {
+                                        RpErr::OpenFileErr { file: f, err: err.to_string() }.termination();
+                                    }
+                                }
+                            })
+                            .map(|(fin, f)| (encoding(configs).read_records(fin, record_sep(configs)), Rc::new(f)))
+                            .flat_map(|(reader, f)| reader.enumerate().map(move |l| (l, f.clone())))
+                            .filter_map(|((line, lr), f)| match lr {
+                                Ok(item) => {
+                                    set_source(&f, line + 1);
+                                    Some(item)
+                                }
+                                Err(err) => {
+                                    if skip_err(configs) {
+                                        None
+                                    } else {
+                                        RpErr::ReadFromFileErr {
+                                            file: (*f).clone(),
+                                            line_no: line,
+                                            err: err.to_string(),
+                                        }
                                         .termination();
+                                    }
                                 }
-                            }
-                        }),
-                ),
-            }),
+                            })
+                            .chain(from_fn(|| {
+                                clear_source();
+                                None
+                            })),
+                    ),
+                })
+            }
+            Input::Cmd(cmd_arg) => {
+                let mut child = cmd_arg.spawn_reader()?;
+                let stdout = child.stdout.take().expect("stdout of child is piped");
+                let mut child = Some(child);
+                Ok(Pipe {
+                    iter: Box::new(
+                        encoding(configs)
+                            .read_records(stdout, record_sep(configs))
+                            .enumerate()
+                            .filter_map({
+                                let cmd_arg = cmd_arg.clone();
+                                move |(line, lr)| match lr {
+                                    Ok(item) => Some(item),
+                                    Err(err) => {
+                                        if skip_err(configs) {
+                                            None
+                                        } else {
+                                            RpErr::RunCmdErr {
+                                                cmd: cmd_arg.to_string(),
+                                                err: format!("read line {} error: {err}", line + 1),
+                                            }
+                                            .termination();
+                                        }
+                                    }
+                                }
+                            })
+                            .chain(from_fn(move || {
+                                // 读取完成后检查退出状态
+                                if let Some(mut child) = child.take()
+                                    && let Err(err) = cmd_arg.wait(&mut child, configs)
+                                {
+                                    err.termination();
+                                }
+                                None
+                            })),
+                    ),
+                })
+            }
             #[cfg(windows)]
             Input::Clip => match clipboard_win::get_clipboard_string() {
                 Ok(text) => {
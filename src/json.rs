@@ -0,0 +1,122 @@
+use crate::err::RpErr;
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+
+/// JSON路径中的一段
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum JsonSeg {
+    /// 对象的键
+    Key(String),
+    /// 数组的索引，从0开始
+    Index(usize),
+}
+
+/// 简单的JSON路径，例如`.a.b[0]`，`.`表示整个值。
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct JsonPath {
+    path: String,
+    segs: Vec<JsonSeg>,
+}
+
+impl JsonPath {
+    /// 解析JSON路径，路径必须以`.`开头，键为`.`和`[`以外的非空字符，索引为`[`和`]`包裹的非负整数。
+    pub(crate) fn new(path: String, cmd: &'static str) -> Result<JsonPath, RpErr> {
+        let err =
+            |error: &str| RpErr::ArgParseErr { cmd, arg: "<path>", arg_value: path.clone(), error: error.to_string() };
+        let Some(mut rest) = path.strip_prefix('.') else {
+            return Err(err("path must start with '.'"));
+        };
+        let mut segs = Vec::new();
+        let mut first = true;
+        while !rest.is_empty() {
+            if let Some(remaining) = rest.strip_prefix('[') {
+                let (index, remaining) = remaining.split_once(']').ok_or_else(|| err("missing ']'"))?;
+                segs.push(JsonSeg::Index(index.parse().map_err(|_| err("invalid index"))?));
+                rest = remaining;
+            } else {
+                if !first {
+                    rest = rest.strip_prefix('.').ok_or_else(|| err("expect '.' or '['"))?;
+                }
+                let end = rest.find(['.', '[']).unwrap_or(rest.len());
+                if end == 0 {
+                    return Err(err("empty key"));
+                }
+                segs.push(JsonSeg::Key(rest[..end].to_string()));
+                rest = &rest[end..];
+            }
+            first = false;
+        }
+        Ok(JsonPath { path, segs })
+    }
+
+    /// 按照路径获取值，路径不存在时返回`None`。
+    pub(crate) fn get<'a>(&self, value: &'a Value) -> Option<&'a Value> {
+        self.segs.iter().try_fold(value, |value, seg| match seg {
+            JsonSeg::Key(key) => value.get(key),
+            JsonSeg::Index(index) => value.get(index),
+        })
+    }
+
+    /// 将数据解析为JSON并按照路径提取值，字符串值输出原始文本，其他值输出紧凑的JSON文本。
+    pub(crate) fn extract(&self, item: &str) -> Result<Option<String>, RpErr> {
+        let value = parse_json(item)?;
+        Ok(self.get(&value).map(|value| match value {
+            Value::String(string) => string.clone(),
+            value => value.to_string(),
+        }))
+    }
+}
+
+impl Display for JsonPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.path)
+    }
+}
+
+pub(crate) fn parse_json(item: &str) -> Result<Value, RpErr> {
+    serde_json::from_str(item).map_err(|err| RpErr::ParseJsonErr { item: item.to_string(), err: err.to_string() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_path() {
+        let path = |path: &str| JsonPath::new(path.to_string(), ":json");
+        assert_eq!(path(".").unwrap().segs, vec![]);
+        assert_eq!(
+            path(".a.b[0][12].c").unwrap().segs,
+            vec![
+                JsonSeg::Key("a".to_string()),
+                JsonSeg::Key("b".to_string()),
+                JsonSeg::Index(0),
+                JsonSeg::Index(12),
+                JsonSeg::Key("c".to_string())
+            ]
+        );
+        assert_eq!(path(".[1]").unwrap().segs, vec![JsonSeg::Index(1)]);
+        assert!(path("a").is_err());
+        assert!(path(".a..b").is_err());
+        assert!(path(".a[").is_err());
+        assert!(path(".a[-1]").is_err());
+        assert!(path(".a[0]b").is_err());
+    }
+
+    #[test]
+    fn test_extract() {
+        let item = r#"{"a": {"b": [1, "x", null, {"c": true}]}, "s": "text"}"#;
+        let extract = |path: &str| JsonPath::new(path.to_string(), ":json").unwrap().extract(item);
+        assert_eq!(extract(".a.b[0]"), Ok(Some("1".to_string())));
+        assert_eq!(extract(".a.b[1]"), Ok(Some("x".to_string())));
+        assert_eq!(extract(".a.b[2]"), Ok(Some("null".to_string())));
+        assert_eq!(extract(".a.b[3]"), Ok(Some(r#"{"c":true}"#.to_string())));
+        assert_eq!(extract(".s"), Ok(Some("text".to_string())));
+        assert_eq!(extract(".a.b[4]"), Ok(None));
+        assert_eq!(extract(".s.x"), Ok(None));
+        assert_eq!(extract(".x"), Ok(None));
+        let path = JsonPath::new(".".to_string(), ":json").unwrap();
+        assert_eq!(path.extract(" [1, 2] "), Ok(Some("[1,2]".to_string())));
+        assert!(matches!(path.extract("{"), Err(RpErr::ParseJsonErr { .. })));
+    }
+}
@@ -1,20 +1,30 @@
 use crate::config::Config;
 use crate::err::RpErr;
+use crate::input::Input;
+use crate::op::Op;
+use crate::output::Output;
 use crate::pipe::Pipe;
 use std::iter::Peekable;
 use std::str::FromStr;
 
+mod cmd;
+mod compress;
 mod condition;
 mod config;
+mod encoding;
 mod err;
+mod file;
 mod fmt;
 mod help;
 mod input;
+mod json;
 pub(crate) mod op;
+mod out_format;
 mod output;
 mod parse;
 mod pipe;
 pub(crate) mod print;
+mod source;
 
 pub(crate) type Integer = i64;
 pub(crate) type Float = f64;
@@ -52,6 +62,15 @@ impl FromStr for Num {
     }
 }
 
+impl std::fmt::Display for Num {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Num::Integer(integer) => std::fmt::Display::fmt(integer, f),
+            Num::Float(float) => std::fmt::Display::fmt(float, f),
+        }
+    }
+}
+
 impl PartialOrd for Num {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         match (self, other) {
@@ -94,9 +113,43 @@ pub fn run(mut args: Peekable<impl Iterator<Item = String>>) -> Result<(), RpErr
         return Ok(());
     };
     let configs: &'static mut [Config] = configs.leak();
+    if let Some(backup) = config::in_place(configs) {
+        return run_in_place(input, ops, output, backup, configs);
+    }
     let mut pipe = input.try_into(configs)?;
     for op in ops {
         pipe = op.wrap(pipe, configs)?;
     }
-    output.handle(pipe)
+    output.handle(pipe, configs)
+}
+
+/// 原地编辑：对每个文件分别执行流水线，并将结果原子地写回原文件。
+fn run_in_place(
+    input: Input, ops: Vec<Op>, output: Output, backup: Option<&'static str>, configs: &'static [Config],
+) -> Result<(), RpErr> {
+    let file_arg = match input {
+        Input::File(file_arg) if !file_arg.follow => file_arg,
+        input => Err(RpErr::ArgParseErr {
+            cmd: "--in-place",
+            arg: "<input>",
+            arg_value: format!("{input:?}"),
+            error: "input must be `:file` without `follow`".to_string(),
+        })?,
+    };
+    if output != Output::StdOut {
+        Err(RpErr::ArgParseErr {
+            cmd: "--in-place",
+            arg: "<output>",
+            arg_value: format!("{output:?}"),
+            error: "output must be `:to out`".to_string(),
+        })?
+    }
+    for file in file_arg.paths(configs)? {
+        let mut pipe = Input::new_file(file_arg.with_files(vec![file.clone()])).try_into(configs)?;
+        for op in ops.iter().cloned() {
+            pipe = op.wrap(pipe, configs)?;
+        }
+        output::write_in_place(pipe, file, file_arg.compress, backup, configs)?;
+    }
+    Ok(())
 }
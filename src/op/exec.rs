@@ -0,0 +1,192 @@
+use crate::cmd::CmdArg;
+use crate::config::{encoding, record_sep, skip_err, Config};
+use crate::err::RpErr;
+use crate::source::clear_source;
+use std::collections::VecDeque;
+use std::io::Cursor;
+use std::process::{Child, Stdio};
+
+/// 外部命令的结果处理方式
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum ExecMode {
+    /// 使用命令的标准输出替换数据
+    Out,
+    /// 仅保留命令退出状态成功的数据
+    Filter,
+    /// 仅运行命令，保留原数据
+    Peek,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct ExecArg {
+    pub(crate) cmd: CmdArg,
+    pub(crate) mode: ExecMode,
+    /// 每批数据的数量
+    pub(crate) batch: usize,
+    /// 同时运行的命令数量上限
+    pub(crate) parallel: usize,
+}
+
+impl ExecArg {
+    pub(crate) fn new(cmd: CmdArg) -> ExecArg {
+        ExecArg { cmd, mode: ExecMode::Out, batch: 1, parallel: 1 }
+    }
+}
+
+/// 按批运行外部命令的迭代器，同时运行不超过`parallel`个命令，按照数据的顺序输出结果。
+pub(crate) struct ExecIter<I: Iterator<Item = String>> {
+    source: I,
+    arg: ExecArg,
+    configs: &'static [Config],
+    /// 运行中的命令及其对应的数据，命令启动失败且跳过错误时为`None`
+    running: VecDeque<(Vec<String>, Option<Child>)>,
+    /// 已经完成的命令的输出
+    output: std::vec::IntoIter<String>,
+    exhausted: bool,
+}
+
+impl<I: Iterator<Item = String>> ExecIter<I> {
+    pub(crate) fn new(source: I, arg: ExecArg, configs: &'static [Config]) -> ExecIter<I> {
+        ExecIter { source, arg, configs, running: VecDeque::new(), output: Vec::new().into_iter(), exhausted: false }
+    }
+
+    fn spawn(&self, items: &[String]) -> Result<Child, RpErr> {
+        let stdout = match self.arg.mode {
+            ExecMode::Out => Stdio::piped(),
+            ExecMode::Filter => Stdio::null(),
+            ExecMode::Peek => Stdio::inherit(),
+        };
+        self.arg
+            .cmd
+            .command_with(items)
+            .stdin(Stdio::null())
+            .stdout(stdout)
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|err| RpErr::RunCmdErr { cmd: self.arg.cmd.to_string(), err: err.to_string() })
+    }
+
+    fn finish(&self, items: Vec<String>, child: Option<Child>) -> Result<Vec<String>, RpErr> {
+        let Some(child) = child else {
+            return Ok(if self.arg.mode == ExecMode::Peek { items } else { Vec::new() });
+        };
+        let run_err = |err: std::io::Error| RpErr::RunCmdErr { cmd: self.arg.cmd.to_string(), err: err.to_string() };
+        let output = child.wait_with_output().map_err(run_err)?;
+        if !output.status.success() {
+            return match self.arg.mode {
+                ExecMode::Filter => Ok(Vec::new()),
+                _ if !skip_err(self.configs) => {
+                    Err(RpErr::CmdExitErr { cmd: self.arg.cmd.to_string(), status: output.status.to_string() })
+                }
+                ExecMode::Out => Ok(Vec::new()),
+                ExecMode::Peek => Ok(items),
+            };
+        }
+        match self.arg.mode {
+            ExecMode::Out => {
+                let mut res = Vec::new();
+                for record in encoding(self.configs).read_records(Cursor::new(output.stdout), record_sep(self.configs))
+                {
+                    match record {
+                        Ok(record) => res.push(record),
+                        Err(err) if !skip_err(self.configs) => Err(run_err(err))?,
+                        Err(_) => {}
+                    }
+                }
+                Ok(res)
+            }
+            ExecMode::Filter | ExecMode::Peek => Ok(items),
+        }
+    }
+}
+
+impl<I: Iterator<Item = String>> Iterator for ExecIter<I> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.output.next() {
+                return Some(item);
+            }
+            while !self.exhausted && self.running.len() < self.arg.parallel {
+                let items: Vec<String> = self.source.by_ref().take(self.arg.batch).collect();
+                self.exhausted = items.len() < self.arg.batch;
+                if items.is_empty() {
+                    break;
+                }
+                match self.spawn(&items) {
+                    Ok(child) => self.running.push_back((items, Some(child))),
+                    Err(err) => {
+                        if !skip_err(self.configs) {
+                            err.termination();
+                        }
+                        self.running.push_back((items, None));
+                    }
+                }
+            }
+            if self.arg.batch > 1 || self.arg.parallel > 1 {
+                // 预读了多个数据，当前来源不再对应输出的数据
+                clear_source();
+            }
+            let (items, child) = self.running.pop_front()?;
+            match self.finish(items, child) {
+                Ok(output) => self.output = output.into_iter(),
+                Err(err) => err.termination(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::*;
+    use crate::source::{current_source, set_source};
+    use std::rc::Rc;
+
+    fn exec(mode: ExecMode, batch: usize, parallel: usize, cmd: &[&str], items: &[&str]) -> Vec<String> {
+        let arg =
+            ExecArg { mode, batch, parallel, ..ExecArg::new(CmdArg::new(cmd.iter().map(|s| s.to_string()).collect())) };
+        ExecIter::new(items.iter().map(|s| s.to_string()), arg, &[]).collect()
+    }
+
+    #[test]
+    fn test_exec() {
+        assert_eq!(exec(ExecMode::Out, 1, 1, &["echo", "<{v}>"], &["a", "b"]), vec!["<a>", "<b>"]);
+        assert_eq!(exec(ExecMode::Out, 2, 1, &["echo"], &["a", "b", "c"]), vec!["a b", "c"]);
+        assert_eq!(exec(ExecMode::Out, 1, 1, &["printf", "%s\\n%s\\n", "{v}", "{v}"], &["a"]), vec!["a", "a"]);
+        assert_eq!(
+            exec(ExecMode::Out, 1, 3, &["sh", "-c", "sleep 0.$1; echo $1", "-"], &["3", "1", "2", "0"]),
+            vec!["3", "1", "2", "0"]
+        );
+        assert_eq!(exec(ExecMode::Filter, 1, 2, &["test", "-n"], &["a", "", "b"]), vec!["a", "b"]);
+        assert_eq!(exec(ExecMode::Peek, 2, 1, &["true"], &["a", "b", "c"]), vec!["a", "b", "c"]);
+        assert!(exec(ExecMode::Out, 1, 1, &["echo"], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_exec_spawn_err() {
+        let exec = |mode: ExecMode| {
+            let arg = ExecArg { mode, parallel: 2, ..ExecArg::new(CmdArg::new(vec!["rp-no-such-program".to_string()])) };
+            ExecIter::new(["a", "b"].into_iter().map(String::from), arg, &[Config::SkipErr]).collect::<Vec<_>>()
+        };
+        assert_eq!(exec(ExecMode::Peek), vec!["a", "b"]);
+        assert!(exec(ExecMode::Out).is_empty());
+        assert!(exec(ExecMode::Filter).is_empty());
+    }
+
+    #[test]
+    fn test_exec_source() {
+        let file = Rc::new("a.log".to_string());
+        let sources = |batch: usize| {
+            let items = ["a", "b", "c"].into_iter().enumerate().map(|(idx, item)| {
+                set_source(&file, idx + 1);
+                item.to_string()
+            });
+            let arg = ExecArg { mode: ExecMode::Peek, batch, ..ExecArg::new(CmdArg::new(vec!["true".to_string()])) };
+            ExecIter::new(items, arg, &[]).map(|_| current_source().map(|(_, line)| line)).collect::<Vec<_>>()
+        };
+        assert_eq!(sources(1), vec![Some(1), Some(2), Some(3)]);
+        assert_eq!(sources(2), vec![None, None, None]);
+    }
+}
@@ -0,0 +1,142 @@
+use crate::Integer;
+use std::borrow::Cow;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum FieldSep {
+    /// 指定分隔字符串
+    Str(String),
+    /// CSV格式
+    Csv,
+    /// 制表符
+    Tsv,
+    /// 空白字符
+    Ws,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FieldArg {
+    /// 字段索引范围，单个索引使用起止相同的范围表示，负数表示从末尾开始计数
+    ranges: Vec<(Option<Integer>, Option<Integer>)>,
+    sep: FieldSep,
+    join: Option<String>,
+}
+
+impl FieldArg {
+    pub(crate) fn new(
+        ranges: Vec<(Option<Integer>, Option<Integer>)>, sep: FieldSep, join: Option<String>,
+    ) -> FieldArg {
+        FieldArg { ranges, sep, join }
+    }
+
+    /// 拆分字段，保留指定索引的字段，并使用输出分隔字符串重新合并
+    pub(crate) fn extract(&self, text: &str) -> String {
+        let fields: Vec<Cow<str>> = match &self.sep {
+            FieldSep::Str(delimiter) => text.split(delimiter.as_str()).map(Cow::Borrowed).collect(),
+            FieldSep::Csv => split_csv(text),
+            FieldSep::Tsv => text.split('\t').map(Cow::Borrowed).collect(),
+            FieldSep::Ws => text.split_whitespace().map(Cow::Borrowed).collect(),
+        };
+        let join = match (&self.join, &self.sep) {
+            (Some(join), _) => join.as_str(),
+            (None, FieldSep::Str(delimiter)) => delimiter.as_str(),
+            (None, FieldSep::Csv) => ",",
+            (None, FieldSep::Tsv) => "\t",
+            (None, FieldSep::Ws) => " ",
+        };
+        let mut result = String::new();
+        let mut first = true;
+        for idx in self.ranges.iter().flat_map(|range| resolve_range(*range, fields.len())) {
+            if !first {
+                result.push_str(join);
+            }
+            first = false;
+            if self.sep == FieldSep::Csv {
+                quote_csv(&fields[idx], join, &mut result);
+            } else {
+                result.push_str(&fields[idx]);
+            }
+        }
+        result
+    }
+}
+
+/// 将可能为负数的索引范围转换为实际的索引范围，超出字段数量的部分会被忽略
+fn resolve_range((start, end): (Option<Integer>, Option<Integer>), len: usize) -> std::ops::Range<usize> {
+    let len = len as Integer;
+    let resolve = |idx: Integer| if idx < 0 { idx + len } else { idx };
+    let start = start.map(resolve).unwrap_or(0).max(0);
+    let end = end.map(resolve).unwrap_or(len - 1).min(len - 1);
+    if start > end { 0..0 } else { start as usize..end as usize + 1 }
+}
+
+/// 按照CSV格式拆分字段，双引号包裹的字段中可以包含逗号，连续两个双引号表示一个双引号字符
+pub(crate) fn split_csv(text: &str) -> Vec<Cow<'_, str>> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.next_if_eq(&'"').is_some() {
+                    field.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' if field.is_empty() => in_quotes = true,
+            ',' if !in_quotes => fields.push(Cow::Owned(std::mem::take(&mut field))),
+            c => field.push(c),
+        }
+    }
+    fields.push(Cow::Owned(field));
+    fields
+}
+
+/// 按照CSV格式输出字段，字段中包含分隔字符串、双引号或换行符时使用双引号包裹
+pub(crate) fn quote_csv(field: &str, join: &str, result: &mut String) {
+    if field.contains(['"', '\n', '\r']) || (!join.is_empty() && field.contains(join)) {
+        result.push('"');
+        result.push_str(&field.replace('"', "\"\""));
+        result.push('"');
+    } else {
+        result.push_str(field);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_ws() {
+        let arg = FieldArg::new(vec![(Some(0), Some(0)), (Some(-1), Some(-1))], FieldSep::Ws, None);
+        assert_eq!(arg.extract("  a b\tc  d "), "a d");
+        assert_eq!(arg.extract("a"), "a a");
+        assert_eq!(arg.extract(""), "");
+        let arg = FieldArg::new(vec![(Some(5), Some(5)), (Some(-5), Some(-5)), (Some(1), None)], FieldSep::Ws, None);
+        assert_eq!(arg.extract("a b c"), "b c");
+    }
+
+    #[test]
+    fn test_extract_sep() {
+        let arg = FieldArg::new(vec![(Some(1), Some(2))], FieldSep::Str("::".to_owned()), None);
+        assert_eq!(arg.extract("a::b::c::d"), "b::c");
+        let arg = FieldArg::new(vec![(None, Some(-2)), (Some(-1), None)], FieldSep::Str(",".to_owned()), None);
+        assert_eq!(arg.extract("a,b,,d"), "a,b,,d");
+        let arg = FieldArg::new(vec![(Some(2), Some(1))], FieldSep::Str(",".to_owned()), None);
+        assert_eq!(arg.extract("a,b,c"), "");
+        let arg = FieldArg::new(vec![(Some(-1), None), (Some(0), Some(0))], FieldSep::Tsv, Some(" | ".to_owned()));
+        assert_eq!(arg.extract("a\tb\tc"), "c | a");
+    }
+
+    #[test]
+    fn test_extract_csv() {
+        assert_eq!(split_csv(r#"a,"b,c","d""e",,"""#), vec!["a", "b,c", r#"d"e"#, "", ""]);
+        assert_eq!(split_csv(r#""unclosed,x"#), vec!["unclosed,x"]);
+        let arg = FieldArg::new(vec![(Some(1), Some(2))], FieldSep::Csv, None);
+        assert_eq!(arg.extract(r#"a,"b,c","d""e",f"#), r#""b,c","d""e""#);
+        let arg = FieldArg::new(vec![(Some(1), Some(2))], FieldSep::Csv, Some(";".to_owned()));
+        assert_eq!(arg.extract(r#"a,"b,c","d;e",f"#), r#"b,c;"d;e""#);
+    }
+}
@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use unicase::UniCase;
+
+/// 统计每个不同数据的出现次数，结果按照数据首次出现的顺序排列，数据取首次出现时的原始文本
+pub(crate) fn count_freq(items: impl Iterator<Item = String>, nocase: bool) -> Vec<(String, usize)> {
+    if nocase { count_by(items, |item| UniCase::new(item.clone())) } else { count_by(items, String::clone) }
+}
+
+fn count_by<K: Hash + Eq>(items: impl Iterator<Item = String>, key_fn: impl Fn(&String) -> K) -> Vec<(String, usize)> {
+    let mut indexes: HashMap<K, usize> = HashMap::new();
+    let mut counts: Vec<(String, usize)> = vec![];
+    for item in items {
+        match indexes.get(&key_fn(&item)) {
+            Some(&idx) => counts[idx].1 += 1,
+            None => {
+                indexes.insert(key_fn(&item), counts.len());
+                counts.push((item, 1));
+            }
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items(values: &[&str]) -> impl Iterator<Item = String> {
+        values.iter().map(|v| v.to_string()).collect::<Vec<_>>().into_iter()
+    }
+
+    #[test]
+    fn test_count_freq() {
+        assert_eq!(
+            count_freq(items(&["b", "a", "B", "b", "a", "c"]), false),
+            vec![("b".to_string(), 2), ("a".to_string(), 2), ("B".to_string(), 1), ("c".to_string(), 1)]
+        );
+        assert_eq!(
+            count_freq(items(&["b", "a", "B", "b", "a", "c"]), true),
+            vec![("b".to_string(), 3), ("a".to_string(), 2), ("c".to_string(), 1)]
+        );
+        assert_eq!(count_freq(items(&[]), true), vec![]);
+    }
+}
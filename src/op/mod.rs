@@ -1,34 +1,58 @@
+pub(crate) mod exec;
+pub(crate) mod field;
+mod freq;
 mod replace;
 mod slice;
+pub(crate) mod split;
+pub(crate) mod stats;
 pub(crate) mod trim;
 
+use crate::compress::{Compress, CompressWriter};
 use crate::condition::Condition;
-use crate::config::{is_nocase, Config};
+use crate::config::{encoding, is_nocase, record_sep, skip_err, Config};
+use crate::encoding::TextEncoding;
 use crate::err::RpErr;
-use crate::op::replace::ReplaceArg;
+use crate::fmt::{fmt_args, FmtArg};
+use crate::json::JsonPath;
+use crate::out_format::{Formatter, OutFormat};
+use crate::op::exec::{ExecArg, ExecIter};
+use crate::op::field::FieldArg;
+use crate::op::freq::count_freq;
+use crate::op::replace::{RegexReplaceArg, ReplaceArg};
 use crate::op::slice::SliceIter;
+use crate::op::split::SplitArg;
+use crate::op::stats::{stats, StatsKind};
 use crate::op::trim::TrimArg;
 use crate::pipe::Pipe;
-use crate::{Float, Integer, PipeRes};
+use crate::source::{clear_source, current_source};
+use crate::{Float, Integer, Num, PipeRes};
 use cmd_help::CmdHelp;
 use itertools::Itertools;
 use ordered_float::OrderedFloat;
 use rand::seq::SliceRandom;
+use regex::{Regex, RegexBuilder};
 use std::borrow::Cow;
 use std::cmp::Reverse;
 use std::collections::HashSet;
 use std::fs::OpenOptions;
+use std::io;
 use std::io::Write;
 use unicase::UniCase;
 
-#[derive(Debug, PartialEq, CmdHelp)]
+#[derive(Debug, Clone, PartialEq, CmdHelp)]
 pub(crate) enum Op {
     /* **************************************** 访问 **************************************** */
     /// :peek       打印每个值到标准输出或文件。
-    ///             :peek[ <file>][ append][ lf|crlf]
-    ///                 <file>  文件路径，可选。
+    ///             :peek[ <file>][ append][ lf|crlf][ gzip|zstd|bzip2|xz|plain][ <level>]
+    ///             :peek json|jsonl|csv <header>
+    ///                 <file>  文件路径，可选，如果为'json'、'jsonl'或'csv'，需要添加路径前缀，例如'./json'。
     ///                 append  追加输出而不是覆盖，可选，如果未指定则覆盖源文件。
-    ///                 lf|crlf 指定换行符为'LF'或'CRLF'，可选，如果未指定则默认使用'LF'。
+    ///                 lf|crlf 指定换行符为'LF'或'CRLF'，可选，如果未指定则使用全局记录分隔符，默认为'LF'。
+    ///                 gzip|zstd|bzip2|xz|plain
+    ///                         指定压缩格式，可选，参考`:to file`。
+    ///                 <level> 压缩级别，可选，参考`:to file`。
+    ///                 json|jsonl|csv <header>
+    ///                         按照结构化格式打印到标准输出，参考`:to json`、`:to jsonl`和`:to csv`。
     ///             例如：
     ///                 :peek
     ///                 :peek file.txt
@@ -36,6 +60,11 @@ pub(crate) enum Op {
     ///                 :peek file.txt lf
     ///                 :peek file.txt crlf
     ///                 :peek file.txt append crlf
+    ///                 :peek file.txt.gz
+    ///                 :peek file.txt.gz 9
+    ///                 :peek file.txt gzip 9
+    ///                 :peek jsonl
+    ///                 :peek csv name,age
     Peek(PeekArg),
     /* **************************************** 转换 **************************************** */
     /// :upper      转为ASCII大写。
@@ -54,6 +83,19 @@ pub(crate) enum Op {
     ///                 :replace abc xyz nocase
     ///                 :replace abc xyz 10 nocase
     Replace(ReplaceArg),
+    /// :replacer   使用正则表达式替换字符串。
+    ///             :replacer <regex> <to>[ <count>][ nocase]
+    ///                 <regex> 待替换的正则表达式，必选。
+    ///                 <to>    待替换为的字符串，必选。
+    ///                         支持使用`$0`引用整个匹配，使用`$1`、`${name}`引用捕获组。
+    ///                 <count> 对每个元素需要替换的次数，必须为正整数，可选，未指定则替换所有。
+    ///                 nocase  替换时忽略大小写，可选，未指定时不忽略大小写。
+    ///             例如：
+    ///                 :replacer '\d+' '#'
+    ///                 :replacer '(\w+)=(\w+)' '$2=$1'
+    ///                 :replacer '(?<k>\w+)=(?<v>\w+)' '${v}:${k}' 1
+    ///                 :replacer abc xyz nocase
+    RegexReplace(RegexReplaceArg),
     /// :trim       去除首尾指定的子串。
     ///             :trim[ <pattern>[ nocase]]
     ///                 <pattern>   需要去除的子串，可选，留空则去除空白字符。
@@ -88,6 +130,100 @@ pub(crate) enum Op {
     ///             :rtrimr <regex>
     ///                 <regex>     需要去除的正则，必选。
     Trim(TrimArg),
+    /// :field      提取字段，将每个数据拆分为多个字段，保留指定索引的字段并重新合并。
+    ///             字段索引从0开始，负数索引从末尾开始计数，例如-1表示最后一个字段。
+    ///             字段严格按照给定的索引顺序输出，超出范围的索引会被忽略。
+    ///             :field <index>[ <index>][...][ sep <delimiter>|csv|tsv|ws][ join <out>]
+    ///                 <index>     字段索引或索引范围，至少指定一个，范围格式：<start>,<end>。
+    ///                     <start> 范围起始索引，包含，与<end>至少指定一个。
+    ///                     <end>   范围结束索引，包含，与<start>至少指定一个。
+    ///                 sep <delimiter>
+    ///                             按照指定的分隔字符串拆分字段。
+    ///                 csv         按照CSV格式拆分字段，支持双引号包裹的字段，输出时按需添加双引号。
+    ///                 tsv         按照制表符拆分字段。
+    ///                 ws          按照空白字符拆分字段，未指定拆分方式时默认使用此方式。
+    ///                 join <out>  合并字段时使用的分隔字符串，可选，未指定时：sep使用<delimiter>，
+    ///                             csv使用`,`，tsv使用制表符，ws使用单个空格。
+    ///             例如：
+    ///                 :field 0
+    ///                 :field -1 0 sep ,
+    ///                 :field 1,3 csv
+    ///                 :field 2, tsv join ,
+    Field(FieldArg),
+    /// :json       将数据解析为JSON，按照路径提取值，用于处理JSON Lines数据。
+    ///             :json <path>
+    ///                 <path>  JSON路径，必选，以`.`开头，对象的键之间使用`.`分隔，
+    ///                         数组索引使用`[<index>]`，例如`.a.b[0]`，`.`表示整个值。
+    ///             字符串值输出原始文本，其他值输出紧凑的JSON文本，路径不存在时丢弃此数据。
+    ///             数据不是有效的JSON时终止，如果全局配置了跳过错误，则丢弃此数据。
+    ///             例如：
+    ///                 :json .msg
+    ///                 :json .items[0].id
+    Json { path: JsonPath },
+    /// :number     为数据编号，按照格式化字符串输出编号和数据。
+    ///             :number[ <start>[,<step>]][ <fmt>]
+    ///                 <start> 起始编号，可选，未指定时从1开始。
+    ///                 <step>  编号步长，可选，未指定时取步长为1。
+    ///                         如果步长为负值，表示编号递减。
+    ///                 <fmt>   格式化字符串，以{i}表示编号，以{v}表示数据，可选，未指定时取`{i} {v}`。
+    ///                         格式化失败时终止，如果全局配置了跳过错误，则丢弃此数据。
+    ///                         更多格式化信息参考`-h fmt`。
+    ///             例如：
+    ///                 :number
+    ///                 :number 0
+    ///                 :number 10,-1
+    ///                 :number '{i:>3}. {v}'
+    ///                 :number 1,2 '{v} #{i}'
+    Number { start: Integer, step: Integer, fmt: Option<String> },
+    /// :fmt        格式化数据，按照格式化字符串输出每个数据。
+    ///             :fmt <template>
+    ///                 <template>  格式化字符串，必选，支持以下参数：
+    ///                             {v} 数据原始文本。
+    ///                             {n} 数据对应的数值，仅当数据可以解析为数值时可用，例如：{n:08.3}。
+    ///                             {i} 数据索引，从0开始。
+    ///                             {file}  数据来源的文件名，来源为标准输入时为'-'。
+    ///                             {line}  数据在来源中的行号，从1开始。全局记录分隔符不是换行符时为记录的序号。
+    ///                             仅从文件或标准输入读取的数据有来源，否则{file}和{line}为空。
+    ///                             需要读取全部数据的操作（例如:sort、:freq）之后，来源同样为空；
+    ///                             预读多个数据的操作（:exec batch或parallel大于1、:join <batch>大于1）之后，来源同样为空。
+    ///                             格式化失败时终止，如果全局配置了跳过错误，则丢弃此数据。
+    ///                             更多格式化信息参考`-h fmt`。
+    ///             例如：
+    ///                 :fmt '[{v}]'
+    ///                 :fmt '{i:>3}: {n:>10.2}'
+    ///                 :fmt '{file}:{line}: {v}'
+    Fmt { fmt: String },
+    /// :tag        为数据添加前缀，通常用于标记数据来源。
+    ///             :tag[ <template>]
+    ///                 <template>  前缀的格式化字符串，可选，支持的参数与`:fmt`相同。
+    ///                             未指定时为'{file}:{line}: '。
+    ///             例如：
+    ///                 :tag
+    ///                 :tag '[{file}] '
+    Tag { fmt: String },
+    /// :exec       对每个数据或每批数据运行外部命令，类似`xargs`。
+    ///             :exec[ out|filter|peek][ batch <n>][ parallel <n>] <program>[ <arg>][...]
+    ///                 out     使用命令的标准输出替换数据，按照全局记录分隔符拆分为多个数据，默认。
+    ///                 filter  仅保留命令退出状态成功的数据，丢弃命令的标准输出。
+    ///                 peek    仅为了副作用运行命令，保留原数据，命令的标准输出直接输出。
+    ///                 batch <n>
+    ///                         每次运行命令处理的数据数量，必须为正整数，可选，默认为1。
+    ///                 parallel <n>
+    ///                         同时运行的命令数量上限，必须为正整数，可选，默认为1。
+    ///                         并行运行时依然按照数据的顺序输出结果。
+    ///                 <program>   程序名称或路径，必选，如果与选项相同，需要添加路径前缀，例如'./peek'。
+    ///                 <arg>       程序参数，可选，其中的{v}替换为数据，如果以':'开头，需要使用'\:'转义。
+    ///                             每批有多个数据时，包含{v}的参数按照每个数据重复展开。
+    ///                             如果所有参数都不包含{v}，则将数据追加为最后的参数。
+    ///             直接启动程序而不经过shell，程序的标准错误直接输出，标准输入为空。
+    ///             命令启动失败，或者out和peek模式下命令退出状态失败时终止，如果全局配置了跳过错误，
+    ///             则out和filter模式丢弃此批数据，peek模式保留此批数据。
+    ///             例如：
+    ///                 :exec echo 'item: {v}'
+    ///                 :exec filter test -f {v}
+    ///                 :exec batch 100 parallel 4 wc -l
+    ///                 :exec peek parallel 8 touch {v}.done
+    Exec(ExecArg),
     /* **************************************** 减少 **************************************** */
     /// :limit      保留前N个数据，丢弃后续的其他数据。
     ///             :limit <count>
@@ -140,7 +276,57 @@ pub(crate) enum Op {
     /// :count      统计数据数量。
     ///             :count
     Count,
+    /// :stats      统计数值，每个统计项输出一行结果。
+    ///             尝试将数据解析为数值，整数与浮点数混合时按照浮点数计算。
+    ///             :stats[ sum|avg|min|max|median|stddev|all][...][ <default>]
+    ///                 sum         求和。
+    ///                 avg         平均值。
+    ///                 min         最小值。
+    ///                 max         最大值。
+    ///                 median      中位数，数据数量为偶数时取中间两个数值的平均值。
+    ///                 stddev      总体标准差。
+    ///                 all         全部统计项，未指定任何统计项时默认使用此项。
+    ///                             指定多个统计项时，每行结果以统计项名称开头。
+    ///                             没有任何数值时，除sum外的统计项没有结果。
+    ///                 <default>   无法解析为数值的数据的默认数值，可选，未指定时丢弃无法解析的数据。
+    ///             例如：
+    ///                 :stats
+    ///                 :stats sum
+    ///                 :stats avg max 0
+    ///                 :stats all 0.0
+    Stats { kinds: Vec<StatsKind>, default: Option<Num> },
+    /// :freq       统计每个不同数据的出现次数，每个不同数据输出一行结果。
+    ///             :freq[ nocase][ desc|asc][ <fmt>]
+    ///                 nocase  统计时忽略大小写，可选，未指定时不忽略大小写。
+    ///                         忽略大小写时，结果中的数据取首次出现时的原始文本。
+    ///                 desc    按照出现次数逆序排列，可选。
+    ///                 asc     按照出现次数正序排列，可选。
+    ///                         未指定排序方式时按照数据首次出现的顺序排列，次数相同时也按照此顺序排列。
+    ///                 <fmt>   格式化字符串，以{count}表示出现次数，以{v}表示数据，可选，
+    ///                         未指定时取`{count} {v}`。
+    ///                         格式化失败时终止，如果全局配置了跳过错误，则丢弃此结果。
+    ///                         更多格式化信息参考`-h fmt`。
+    ///             例如：
+    ///                 :freq
+    ///                 :freq nocase desc
+    ///                 :freq asc '{v}: {count}'
+    Freq { nocase: bool, desc: Option<bool> /*None时不排序*/, fmt: Option<String> },
     /* **************************************** 增加 **************************************** */
+    /// :split      拆分数据，将每个数据按照分隔符拆分为多个数据。
+    ///             :split[ <delimiter>[ nocase][ keep-empty]]
+    ///             :split reg <regex>[ nocase][ keep-empty]
+    ///                 <delimiter> 分隔字符串，可选，未指定时按照空白字符拆分。
+    ///                 <regex>     分隔正则表达式，指定`reg`时必选。
+    ///                 nocase      忽略大小写，可选，仅当指定了<delimiter>或<regex>时生效。
+    ///                 keep-empty  保留拆分得到的空字符串，可选，仅当指定了<delimiter>或<regex>时生效，
+    ///                             未指定时丢弃空字符串。
+    ///             例如：
+    ///                 :split
+    ///                 :split ,
+    ///                 :split , keep-empty
+    ///                 :split x nocase
+    ///                 :split reg '\s*[,;]\s*'
+    Split(SplitArg),
     /* **************************************** 调整位置 **************************************** */
     /// :sort       排序。
     ///             :sort[ num [<default>]][ nocase][ desc][ random]
@@ -170,6 +356,30 @@ impl Op {
     pub(crate) fn new_replace(from: String, to: String, count: Option<usize>, nocase: bool) -> Op {
         Op::Replace(ReplaceArg::new(from, to, count, nocase))
     }
+    pub(crate) fn new_regex_replace(reg: String, to: String, count: Option<usize>, nocase: bool) -> Result<Op, RpErr> {
+        Ok(Op::RegexReplace(RegexReplaceArg::new(reg, to, count, nocase)?))
+    }
+    pub(crate) fn new_json(path: JsonPath) -> Op {
+        Op::Json { path }
+    }
+    pub(crate) fn new_number(start: Integer, step: Integer, fmt: Option<String>) -> Op {
+        Op::Number { start, step, fmt }
+    }
+    pub(crate) fn new_tag(fmt: Option<String>) -> Op {
+        Op::Tag { fmt: fmt.unwrap_or_else(|| "{file}:{line}: ".to_string()) }
+    }
+    pub(crate) fn new_exec(exec_arg: ExecArg) -> Op {
+        Op::Exec(exec_arg)
+    }
+    pub(crate) fn new_stats(kinds: Vec<StatsKind>, default: Option<Num>) -> Op {
+        Op::Stats {
+            kinds: if kinds.is_empty() { StatsKind::ALL.to_vec() } else { kinds.into_iter().unique().collect() },
+            default,
+        }
+    }
+    pub(crate) fn new_freq(nocase: bool, desc: Option<bool>, fmt: Option<String>) -> Op {
+        Op::Freq { nocase, desc, fmt }
+    }
     pub(crate) fn new_join(join_info: JoinInfo, count: Option<usize>) -> Op {
         Op::Join { join_info, batch: count }
     }
@@ -183,25 +393,45 @@ impl Op {
     pub(crate) fn wrap(self, mut pipe: Pipe, configs: &'static [Config]) -> PipeRes {
         match self {
             Op::Peek(peek) => match peek {
-                PeekArg::StdOut => Ok(pipe.op_inspect(|item| println!("{item}"))),
-                PeekArg::File { file, append, crlf } => {
-                    match OpenOptions::new().write(true).truncate(!append).append(append).create(true).open(&file) {
-                        Ok(mut writer) => {
-                            let postfix = if crlf.unwrap_or(false) { "\r\n" } else { "\n" };
-                            Ok(pipe.op_inspect(move |item| {
-                                if let Err(err) = write!(writer, "{item}{postfix}") {
-                                    RpErr::WriteToFileErr {
-                                        file: file.clone(),
-                                        item: item.to_string(),
-                                        err: err.to_string(),
-                                    }
-                                    .termination()
-                                }
-                            }))
+                PeekArg::StdOut => {
+                    let encoding = encoding(configs);
+                    let postfix = record_sep(configs).postfix(None);
+                    Ok(pipe.op_inspect(move |item| {
+                        if let Err(err) = encoding.write_record(&mut io::stdout(), item, postfix) {
+                            RpErr::WriteToStdOutErr { item: item.to_string(), err: err.to_string() }.termination()
                         }
+                    }))
+                }
+                PeekArg::File { file, append, crlf, compress, level } => {
+                    match OpenOptions::new()
+                        .write(true)
+                        .truncate(!append)
+                        .append(append)
+                        .create(true)
+                        .open(&file)
+                        .and_then(|fout| Compress::encoder(compress, level, &file, fout))
+                    {
+                        Ok(writer) => Ok(Pipe {
+                            iter: Box::new(FilePeek {
+                                source: pipe,
+                                file,
+                                writer,
+                                encoding: encoding(configs),
+                                postfix: record_sep(configs).postfix(crlf),
+                                finished: false,
+                            }),
+                        }),
                         Err(err) => RpErr::OpenFileErr { file, err: err.to_string() }.termination(),
                     }
                 }
+                PeekArg::Format(format) => Ok(Pipe {
+                    iter: Box::new(FormatPeek {
+                        source: pipe,
+                        writer: io::stdout(),
+                        formatter: Formatter::new(format, encoding(configs), record_sep(configs).postfix(None)),
+                        finished: false,
+                    }),
+                }),
             },
             Op::Case(case_arg) => match case_arg {
                 CaseArg::Lower => Ok(pipe.op_map(|mut item|
@@ -248,7 +478,83 @@ impl Op {
                     }))
                 }
             }
+            Op::RegexReplace(replace_arg) => {
+                if replace_arg.count == Some(0) {
+                    Ok(pipe)
+                } else {
+                    let replace_arg = replace_arg.with_configs(configs)?;
+                    Ok(pipe.op_map(move |item| match replace_arg.replace(&item) {
+                        Cow::Borrowed(_) => item,
+                        Cow::Owned(string) => string,
+                    }))
+                }
+            }
             Op::Trim(trim_arg) => Ok(pipe.op_map(move |s| trim_arg.trim(s, configs))),
+            Op::Field(field_arg) => Ok(pipe.op_map(move |item| field_arg.extract(&item))),
+            Op::Json { path } => Ok(Pipe {
+                iter: Box::new(pipe.filter_map(move |item| match path.extract(&item) {
+                    Ok(value) => value,
+                    Err(err) => {
+                        if skip_err(configs) {
+                            None
+                        } else {
+                            err.termination();
+                        }
+                    }
+                })),
+            }),
+            Op::Number { start, step, fmt } => {
+                let mut next = start;
+                Ok(Pipe {
+                    iter: Box::new(pipe.filter_map(move |item| {
+                        let i = next;
+                        next = next.wrapping_add(step);
+                        if let Some(fmt) = &fmt {
+                            match fmt_args(fmt, &[("i", FmtArg::from(i)), ("v", FmtArg::from(item))]) {
+                                Ok(string) => Some(string),
+                                Err(err) => {
+                                    if skip_err(configs) {
+                                        None
+                                    } else {
+                                        err.termination();
+                                    }
+                                }
+                            }
+                        } else {
+                            Some(format!("{i} {item}"))
+                        }
+                    })),
+                })
+            }
+            Op::Fmt { fmt } => Ok(Pipe {
+                iter: Box::new(pipe.enumerate().filter_map(move |(i, item)| {
+                    match fmt_args(&fmt, &item_fmt_args(i, &item)) {
+                        Ok(string) => Some(string),
+                        Err(err) => {
+                            if skip_err(configs) {
+                                None
+                            } else {
+                                err.termination();
+                            }
+                        }
+                    }
+                })),
+            }),
+            Op::Tag { fmt } => Ok(Pipe {
+                iter: Box::new(pipe.enumerate().filter_map(move |(i, item)| {
+                    match fmt_args(&fmt, &item_fmt_args(i, &item)) {
+                        Ok(prefix) => Some(prefix + &item),
+                        Err(err) => {
+                            if skip_err(configs) {
+                                None
+                            } else {
+                                err.termination();
+                            }
+                        }
+                    }
+                })),
+            }),
+            Op::Exec(exec_arg) => Ok(Pipe { iter: Box::new(ExecIter::new(pipe, exec_arg, configs)) }),
             // OPT 2026-01-22 01:10 针对 limit 0、skip 0 等命令进行优化
             Op::Slice { ranges } => Ok(Pipe { iter: Box::new(SliceIter::new(pipe, ranges)) }),
             Op::Uniq { nocase } => {
@@ -275,12 +581,69 @@ impl Op {
                     ))),
                 })
             }
-            Op::TakeDrop { mode, cond } => match mode {
-                TakeDropMode::Take => Ok(Pipe { iter: Box::new(pipe.filter(move |s| cond.test(s))) }),
-                TakeDropMode::Drop => Ok(Pipe { iter: Box::new(pipe.filter(move |s| !cond.test(s))) }),
-                TakeDropMode::TakeWhile => Ok(Pipe { iter: Box::new(pipe.take_while(move |s| cond.test(s))) }),
-                TakeDropMode::DropWhile => Ok(Pipe { iter: Box::new(pipe.skip_while(move |s| cond.test(s))) }),
-            },
+            Op::TakeDrop { mode, cond } => {
+                let cond = cond.with_configs(configs)?;
+                // 无法测试条件的数据（例如不是有效的JSON）在选择之前丢弃
+                let tested = pipe.filter_map(move |s| match cond.test(&s) {
+                    Ok(selected) => Some((s, selected)),
+                    Err(err) => {
+                        if skip_err(configs) {
+                            None
+                        } else {
+                            err.termination();
+                        }
+                    }
+                });
+                match mode {
+                    TakeDropMode::Take => {
+                        Ok(Pipe { iter: Box::new(tested.filter(|(_, selected)| *selected).map(|(s, _)| s)) })
+                    }
+                    TakeDropMode::Drop => {
+                        Ok(Pipe { iter: Box::new(tested.filter(|(_, selected)| !*selected).map(|(s, _)| s)) })
+                    }
+                    TakeDropMode::TakeWhile => {
+                        Ok(Pipe { iter: Box::new(tested.take_while(|(_, selected)| *selected).map(|(s, _)| s)) })
+                    }
+                    TakeDropMode::DropWhile => {
+                        Ok(Pipe { iter: Box::new(tested.skip_while(|(_, selected)| *selected).map(|(s, _)| s)) })
+                    }
+                }
+            }
+            Op::Stats { kinds, default } => {
+                let nums = pipe.filter_map(|item| item.parse::<Num>().ok().or(default)).collect();
+                Ok(Pipe { iter: Box::new(stats(nums, &kinds).into_iter()) })
+            }
+            Op::Freq { nocase, desc, fmt } => {
+                let mut counts = count_freq(pipe, is_nocase(nocase, configs));
+                match desc {
+                    Some(true) => counts.sort_by_key(|(_, count)| Reverse(*count)),
+                    Some(false) => counts.sort_by_key(|(_, count)| *count),
+                    None => (),
+                }
+                Ok(Pipe {
+                    iter: Box::new(counts.into_iter().filter_map(move |(item, count)| {
+                        if let Some(fmt) = &fmt {
+                            match fmt_args(fmt, &[("count", FmtArg::from(count as Integer)), ("v", FmtArg::from(item))])
+                            {
+                                Ok(string) => Some(string),
+                                Err(err) => {
+                                    if skip_err(configs) {
+                                        None
+                                    } else {
+                                        err.termination();
+                                    }
+                                }
+                            }
+                        } else {
+                            Some(format!("{count} {item}"))
+                        }
+                    })),
+                })
+            }
+            Op::Split(split_arg) => {
+                let split_arg = split_arg.with_configs(configs)?;
+                Ok(pipe.op_flat_map(move |item| split_arg.split(item, configs)))
+            }
             Op::Count => Ok(Pipe { iter: Box::new(std::iter::once(pipe.count().to_string())) }),
             Op::Sort { sort_by, desc } => match sort_by {
                 SortBy::Num(def_integer, def_float) => {
@@ -329,27 +692,35 @@ impl Op {
     }
 }
 
-#[derive(Debug, PartialEq)]
+pub(crate) fn build_regex(reg: &str, nocase: bool) -> Result<Regex, RpErr> {
+    RegexBuilder::new(reg)
+        .case_insensitive(nocase)
+        .build()
+        .map_err(|err| RpErr::ParseRegexErr { reg: reg.to_owned(), err: err.to_string() })
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum CaseArg {
     Upper,
     Lower,
     Switch,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum PeekArg {
     StdOut,
-    File { file: String, append: bool, crlf: Option<bool> },
+    File { file: String, append: bool, crlf: Option<bool>, compress: Option<Compress>, level: Option<u32> },
+    Format(OutFormat),
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum SortBy {
     Num(Option<Integer>, Option<Float>),
     Text(bool /*nocase*/),
     Random,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum TakeDropMode {
     Take,
     Drop,
@@ -357,13 +728,120 @@ pub(crate) enum TakeDropMode {
     DropWhile,
 }
 
-#[derive(Debug, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub(crate) struct JoinInfo {
     pub(crate) delimiter: String,
     pub(crate) prefix: String,
     pub(crate) postfix: String,
 }
 
+/// 单个数据的格式化参数：{v}、{n}、{i}、{file}和{line}。
+fn item_fmt_args(i: usize, item: &str) -> Vec<(&'static str, FmtArg)> {
+    let mut args = vec![("i", FmtArg::from(i as Integer)), ("v", FmtArg::from(item))];
+    if let Ok(num) = item.parse::<Num>() {
+        args.push(("n", FmtArg::from(num)));
+    }
+    match current_source() {
+        Some((file, line_no)) => {
+            args.push(("file", FmtArg::from(&*file)));
+            args.push(("line", FmtArg::from(line_no as Integer)));
+        }
+        None => {
+            args.push(("file", FmtArg::from("")));
+            args.push(("line", FmtArg::from("")));
+        }
+    }
+    args
+}
+
+/// 打印数据到文件，迭代结束或者被提前丢弃时显式完成压缩，完成失败时终止，避免压缩文件被截断。
+struct FilePeek<I: Iterator<Item = String>, W: Write> {
+    source: I,
+    file: String,
+    writer: CompressWriter<W>,
+    encoding: TextEncoding,
+    postfix: &'static str,
+    finished: bool,
+}
+
+impl<I: Iterator<Item = String>, W: Write> FilePeek<I, W> {
+    fn finish(&mut self) {
+        if !self.finished {
+            self.finished = true;
+            if let Err(err) = self.writer.finish() {
+                RpErr::WriteToFileErr { file: self.file.clone(), item: String::new(), err: err.to_string() }
+                    .termination()
+            }
+        }
+    }
+}
+
+impl<I: Iterator<Item = String>, W: Write> Iterator for FilePeek<I, W> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.source.next();
+        match &item {
+            Some(item) => {
+                if let Err(err) = self.encoding.write_record(&mut self.writer, item, self.postfix) {
+                    RpErr::WriteToFileErr { file: self.file.clone(), item: item.clone(), err: err.to_string() }
+                        .termination()
+                }
+            }
+            None => self.finish(),
+        }
+        item
+    }
+}
+
+impl<I: Iterator<Item = String>, W: Write> Drop for FilePeek<I, W> {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+/// 按照结构化格式打印数据，迭代结束或者被提前丢弃（例如之后的`:limit`不再拉取数据）时补全剩余内容。
+struct FormatPeek<I: Iterator<Item = String>, W: Write> {
+    source: I,
+    writer: W,
+    formatter: Formatter,
+    finished: bool,
+}
+
+impl<I: Iterator<Item = String>, W: Write> FormatPeek<I, W> {
+    fn finish(&mut self) {
+        if !self.finished {
+            self.finished = true;
+            if let Err(err) = self.formatter.finish(&mut self.writer).and_then(|_| self.writer.flush()) {
+                RpErr::WriteToStdOutErr { item: String::new(), err: err.to_string() }.termination()
+            }
+        }
+    }
+}
+
+impl<I: Iterator<Item = String>, W: Write> Iterator for FormatPeek<I, W> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.source.next();
+        match &item {
+            Some(item) => {
+                if let Err(err) = self.formatter.write(&mut self.writer, item) {
+                    RpErr::WriteToStdOutErr { item: item.clone(), err: err.to_string() }.termination()
+                }
+            }
+            None => self.finish(),
+        }
+        item
+    }
+}
+
+impl<I: Iterator<Item = String>, W: Write> Drop for FormatPeek<I, W> {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
 struct ChunkJoin<I: Iterator<Item = String>> {
     source: I,
     group_size: usize,
@@ -385,6 +863,10 @@ where
                 break;
             }
         }
+        if self.group_size > 1 {
+            // 合并多个数据后来源不再对应合并的结果
+            clear_source();
+        }
         if chunk.is_empty() {
             None
         } else {
@@ -397,3 +879,102 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::condition::Select;
+    use crate::encoding::RecordSep;
+    use crate::file::FileArg;
+    use crate::input::Input;
+    use std::io::Read;
+
+    #[test]
+    fn test_fmt_num() {
+        let items = ["12", "1.5", "x"].into_iter().map(String::from);
+        let pipe = Op::Fmt { fmt: "{n:08.3}".to_string() }.wrap(Pipe { iter: Box::new(items) }, &[Config::SkipErr]);
+        assert_eq!(pipe.unwrap().collect::<Vec<_>>(), vec!["0012.000", "0001.500"]);
+    }
+
+    #[test]
+    fn test_freq_skip_err() {
+        let items = ["a", "b", "a"].into_iter().map(String::from);
+        let op = Op::Freq { nocase: false, desc: None, fmt: Some("{v:x}".to_string()) };
+        assert!(op.wrap(Pipe { iter: Box::new(items) }, &[Config::SkipErr]).unwrap().next().is_none());
+    }
+
+    #[test]
+    fn test_tag_source() {
+        let root = std::env::temp_dir().join(format!("rp_op_test_tag_source_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        let (a, b) = (root.join("a.log"), root.join("b.log"));
+        std::fs::write(&a, "x\ny\n").unwrap();
+        std::fs::write(&b, "p1\np1\n\n\np2\n").unwrap();
+        let input = |file: &std::path::Path, configs: &'static [Config]| {
+            Input::new_file(FileArg::new(vec![file.to_string_lossy().into_owned()])).try_into(configs).unwrap()
+        };
+        let tag = Op::Tag { fmt: "{file}:{line}: ".to_string() }.wrap(input(&a, &[]), &[]).unwrap();
+        let a_name = a.to_string_lossy();
+        assert_eq!(tag.collect::<Vec<_>>(), vec![format!("{a_name}:1: x"), format!("{a_name}:2: y")]);
+        // 段落模式下{line}为记录序号
+        let configs = &[Config::RecordSep(RecordSep::Para)];
+        let fmt = Op::Fmt { fmt: "{line}|{v}".to_string() }.wrap(input(&b, configs), configs).unwrap();
+        assert_eq!(fmt.collect::<Vec<_>>(), vec!["1|p1\np1", "2|p2"]);
+        // 读取全部数据的操作之后没有来源
+        let sorted = Op::new_sort(SortBy::Text(false), true).wrap(input(&a, &[]), &[]).unwrap();
+        let fmt = Op::Fmt { fmt: "[{file}:{line}] {v}".to_string() }.wrap(sorted, &[]).unwrap();
+        assert_eq!(fmt.collect::<Vec<_>>(), vec!["[:] y", "[:] x"]);
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_take_not_json() {
+        let path = JsonPath::new(".status".to_string(), ":take").unwrap();
+        let num = Select::new_num_range(Some(Num::from(200)), Some(Num::from(299))).yes();
+        let cond = Select::new_json(path, num).not();
+        let items = [r#"{"status": 404}"#, "not json", r#"{"status": 200}"#].into_iter().map(String::from);
+        let pipe = Pipe { iter: Box::new(items.clone()) };
+        let taken = Op::new_take_drop(TakeDropMode::Take, cond.clone()).wrap(pipe, &[Config::SkipErr]).unwrap();
+        assert_eq!(taken.collect::<Vec<_>>(), vec![r#"{"status": 404}"#]);
+        let pipe = Pipe { iter: Box::new(items) };
+        let dropped = Op::new_take_drop(TakeDropMode::Drop, cond).wrap(pipe, &[Config::SkipErr]).unwrap();
+        assert_eq!(dropped.collect::<Vec<_>>(), vec![r#"{"status": 200}"#]);
+    }
+
+    #[test]
+    fn test_format_peek_limit() {
+        let file = std::env::temp_dir().join(format!("rp_op_test_format_peek_limit_{}", std::process::id()));
+        let peek = FormatPeek {
+            source: ["a", "b", "c"].into_iter().map(String::from),
+            writer: std::fs::File::create(&file).unwrap(),
+            formatter: Formatter::new(OutFormat::Json, TextEncoding::Utf8, "\n"),
+            finished: false,
+        };
+        // :limit 2 之后不再拉取数据，丢弃时补全JSON数组
+        let limited = Op::Slice { ranges: vec![(None, Some(1))] }.wrap(Pipe { iter: Box::new(peek) }, &[]).unwrap();
+        assert_eq!(limited.collect::<Vec<_>>(), vec!["a", "b"]);
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "[\"a\",\"b\"]\n");
+        std::fs::remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn test_file_peek_gzip_limit() {
+        let file = std::env::temp_dir().join(format!("rp_op_test_file_peek_gzip_limit_{}.gz", std::process::id()));
+        let file = file.to_string_lossy().to_string();
+        let peek =
+            Op::Peek(PeekArg::File { file: file.clone(), append: false, crlf: None, compress: None, level: None });
+        let items = ["a", "b", "c"].into_iter().map(String::from);
+        let pipe = peek.wrap(Pipe { iter: Box::new(items) }, &[]).unwrap();
+        // :limit 2 之后不再拉取数据，丢弃时完成压缩，写出完整的gzip文件
+        let limited = Op::Slice { ranges: vec![(None, Some(1))] }.wrap(pipe, &[]).unwrap();
+        assert_eq!(limited.collect::<Vec<_>>(), vec!["a", "b"]);
+        let mut text = String::new();
+        Compress::decoder(Some(Compress::Gzip), std::fs::File::open(&file).unwrap())
+            .unwrap()
+            .read_to_string(&mut text)
+            .unwrap();
+        assert_eq!(text, "a\nb\n");
+        std::fs::remove_file(file).unwrap();
+    }
+}
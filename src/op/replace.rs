@@ -1,7 +1,10 @@
 use crate::config::{is_nocase, Config};
+use crate::err::RpErr;
+use crate::op::build_regex;
+use regex::Regex;
 use std::borrow::Cow;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) struct ReplaceArg {
     from: String, /*nocase时需要转为小写*/
     to: String,
@@ -57,6 +60,50 @@ impl ReplaceArg {
     }
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct RegexReplaceArg {
+    regex: Regex,
+    to: String,
+    pub(in crate::op) count: Option<usize>,
+    nocase: bool,
+}
+
+impl PartialEq for RegexReplaceArg {
+    fn eq(&self, other: &Self) -> bool {
+        self.regex.as_str() == other.regex.as_str()
+            && self.to == other.to
+            && self.count == other.count
+            && self.nocase == other.nocase
+    }
+}
+
+impl RegexReplaceArg {
+    pub(crate) fn new(reg: String, to: String, count: Option<usize>, nocase: bool) -> Result<Self, RpErr> {
+        Ok(Self { regex: build_regex(&reg, nocase)?, to, count, nocase })
+    }
+
+    /// 根据全局配置调整大小写敏感性，仅当全局忽略大小写而自身未指定`nocase`时需要重新编译正则
+    pub(in crate::op) fn with_configs(self, configs: &[Config]) -> Result<Self, RpErr> {
+        if !self.nocase && is_nocase(self.nocase, configs) {
+            Ok(Self { regex: build_regex(self.regex.as_str(), true)?, nocase: true, ..self })
+        } else {
+            Ok(self)
+        }
+    }
+
+    /// 使用正则替换字符串，替换文本支持`$0`、`$1`、`${name}`等捕获组引用
+    ///
+    /// # Arguments
+    /// * `text` - 原始字符串
+    ///
+    /// # Returns
+    /// 返回替换后的字符串（如果无替换发生，返回原字符串的引用以避免分配）
+    pub(crate) fn replace<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        // `replacen`的`limit`为0时表示替换所有
+        self.regex.replacen(text, self.count.unwrap_or(0), self.to.as_str())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -98,4 +145,58 @@ mod tests {
             "abc_好世界，_好！"
         );
     }
+
+    #[test]
+    fn test_regex_replace() {
+        let configs = vec![];
+        assert!(RegexReplaceArg::new("[".to_owned(), "".to_owned(), None, false).is_err());
+        assert_eq!(
+            RegexReplaceArg::new(r"\d+".to_owned(), "#".to_owned(), None, false).unwrap().replace("a1b22c333"),
+            "a#b#c#"
+        );
+        assert_eq!(
+            RegexReplaceArg::new(r"\d+".to_owned(), "#".to_owned(), Some(2), false).unwrap().replace("a1b22c333"),
+            "a#b#c333"
+        );
+        assert_eq!(
+            RegexReplaceArg::new(r"(\w+)=(\w+)".to_owned(), "$2=$1".to_owned(), None, false)
+                .unwrap()
+                .replace("k1=v1 k2=v2"),
+            "v1=k1 v2=k2"
+        );
+        assert_eq!(
+            RegexReplaceArg::new(r"(?<key>\w+)=(?<value>\w+)".to_owned(), "${value}:${key}".to_owned(), None, false)
+                .unwrap()
+                .replace("k1=v1"),
+            "v1:k1"
+        );
+        assert_eq!(
+            RegexReplaceArg::new(r"\d+".to_owned(), "<$0>".to_owned(), None, false).unwrap().replace("a1b22"),
+            "a<1>b<22>"
+        );
+        assert_eq!(
+            RegexReplaceArg::new("abc".to_owned(), "x".to_owned(), None, false).unwrap().replace("abc ABC"),
+            "x ABC"
+        );
+        assert_eq!(
+            RegexReplaceArg::new("abc".to_owned(), "x".to_owned(), None, true).unwrap().replace("abc ABC"),
+            "x x"
+        );
+        assert_eq!(
+            RegexReplaceArg::new("abc".to_owned(), "x".to_owned(), None, false)
+                .unwrap()
+                .with_configs(&[Config::Nocase])
+                .unwrap()
+                .replace("abc ABC"),
+            "x x"
+        );
+        assert!(matches!(
+            RegexReplaceArg::new("abc".to_owned(), "x".to_owned(), None, false)
+                .unwrap()
+                .with_configs(&configs)
+                .unwrap()
+                .replace("ABC"),
+            Cow::Borrowed(_)
+        ));
+    }
 }
@@ -0,0 +1,163 @@
+use crate::config::{is_nocase, Config};
+use crate::err::RpErr;
+use crate::op::build_regex;
+use regex::Regex;
+
+#[derive(Debug, Clone)]
+pub(crate) enum SplitParam {
+    Blank,
+    Str(String),
+    Regex(Regex),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SplitArg {
+    param: SplitParam,
+    nocase: bool,
+    keep_empty: bool,
+}
+
+impl PartialEq for SplitParam {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (SplitParam::Blank, SplitParam::Blank) => true,
+            (SplitParam::Str(l), SplitParam::Str(r)) => l == r,
+            (SplitParam::Regex(l), SplitParam::Regex(r)) => l.as_str() == r.as_str(),
+            (_, _) => false,
+        }
+    }
+}
+
+impl SplitArg {
+    pub(crate) fn new_blank() -> SplitArg {
+        SplitArg { param: SplitParam::Blank, nocase: false, keep_empty: false }
+    }
+    pub(crate) fn new_str(mut delimiter: String, nocase: bool, keep_empty: bool) -> SplitArg {
+        if nocase {
+            delimiter.make_ascii_lowercase();
+        }
+        SplitArg { param: SplitParam::Str(delimiter), nocase, keep_empty }
+    }
+    pub(crate) fn new_regex(reg: String, nocase: bool, keep_empty: bool) -> Result<SplitArg, RpErr> {
+        Ok(SplitArg { param: SplitParam::Regex(build_regex(&reg, nocase)?), nocase, keep_empty })
+    }
+
+    /// 根据全局配置调整大小写敏感性，字符串模式需要转为小写，正则模式需要重新编译正则
+    pub(in crate::op) fn with_configs(self, configs: &[Config]) -> Result<SplitArg, RpErr> {
+        match self.param {
+            SplitParam::Str(delimiter) if !self.nocase && is_nocase(self.nocase, configs) => {
+                Ok(SplitArg::new_str(delimiter, true, self.keep_empty))
+            }
+            SplitParam::Regex(regex) if !self.nocase && is_nocase(self.nocase, configs) => {
+                Ok(SplitArg { param: SplitParam::Regex(build_regex(regex.as_str(), true)?), nocase: true, ..self })
+            }
+            _ => Ok(self),
+        }
+    }
+
+    pub(crate) fn split(&self, text: String, configs: &[Config]) -> Vec<String> {
+        let parts: Vec<&str> = match &self.param {
+            SplitParam::Blank => return text.split_whitespace().map(String::from).collect(),
+            SplitParam::Str(delimiter) => {
+                if is_nocase(self.nocase, configs) {
+                    // ASCII大小写转换不改变字节位置，可以直接使用转换后的匹配位置截取原始字符串
+                    let lower_text = text.to_ascii_lowercase();
+                    let mut parts = Vec::new();
+                    let mut last_end = 0;
+                    for (start, matched) in lower_text.match_indices(delimiter.as_str()) {
+                        parts.push(&text[last_end..start]);
+                        last_end = start + matched.len();
+                    }
+                    parts.push(&text[last_end..]);
+                    parts
+                } else {
+                    text.split(delimiter.as_str()).collect()
+                }
+            }
+            SplitParam::Regex(regex) => regex.split(&text).collect(),
+        };
+        parts.into_iter().filter(|part| self.keep_empty || !part.is_empty()).map(String::from).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_blank() {
+        let configs = vec![];
+        assert_eq!(SplitArg::new_blank().split(" a  b\tc \n".to_owned(), &configs), vec!["a", "b", "c"]);
+        assert_eq!(SplitArg::new_blank().split("".to_owned(), &configs), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_split_str() {
+        let configs = vec![];
+        assert_eq!(
+            SplitArg::new_str(",".to_owned(), false, false).split("a,b,,c,".to_owned(), &configs),
+            vec!["a", "b", "c"]
+        );
+        assert_eq!(
+            SplitArg::new_str(",".to_owned(), false, true).split("a,b,,c,".to_owned(), &configs),
+            vec!["a", "b", "", "c", ""]
+        );
+        assert_eq!(
+            SplitArg::new_str("x".to_owned(), false, false).split("1x2X3".to_owned(), &configs),
+            vec!["1", "2X3"]
+        );
+        assert_eq!(
+            SplitArg::new_str("X".to_owned(), true, false).split("1x2X3".to_owned(), &configs),
+            vec!["1", "2", "3"]
+        );
+        assert_eq!(
+            SplitArg::new_str("x".to_owned(), false, false).split("1x2X3".to_owned(), &[Config::Nocase]),
+            vec!["1", "2", "3"]
+        );
+        assert_eq!(
+            SplitArg::new_str("X".to_owned(), false, false)
+                .with_configs(&[Config::Nocase])
+                .unwrap()
+                .split("aXbxc".to_owned(), &[Config::Nocase]),
+            vec!["a", "b", "c"]
+        );
+        assert_eq!(
+            SplitArg::new_str("，".to_owned(), true, false).split("你，好，".to_owned(), &configs),
+            vec!["你", "好"]
+        );
+        assert_eq!(
+            SplitArg::new_str("".to_owned(), false, false).split("abc".to_owned(), &configs),
+            vec!["a", "b", "c"]
+        );
+    }
+
+    #[test]
+    fn test_split_regex() {
+        let configs = vec![];
+        assert!(SplitArg::new_regex("[".to_owned(), false, false).is_err());
+        assert_eq!(
+            SplitArg::new_regex(r"\s*[,;]\s*".to_owned(), false, false)
+                .unwrap()
+                .split("a , b;c;;d".to_owned(), &configs),
+            vec!["a", "b", "c", "d"]
+        );
+        assert_eq!(
+            SplitArg::new_regex(r"\s*[,;]\s*".to_owned(), false, true)
+                .unwrap()
+                .split("a , b;c;;d".to_owned(), &configs),
+            vec!["a", "b", "c", "", "d"]
+        );
+        assert_eq!(
+            SplitArg::new_regex("and".to_owned(), true, false).unwrap().split("1AND2and3".to_owned(), &configs),
+            vec!["1", "2", "3"]
+        );
+        assert_eq!(
+            SplitArg::new_regex("and".to_owned(), false, false)
+                .unwrap()
+                .with_configs(&[Config::Nocase])
+                .unwrap()
+                .split("1AND2and3".to_owned(), &configs),
+            vec!["1", "2", "3"]
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{Float, Num};
+use std::cmp::Ordering;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub(crate) enum StatsKind {
+    Sum,
+    Avg,
+    Min,
+    Max,
+    Median,
+    Stddev,
+}
+
+impl StatsKind {
+    pub(crate) const ALL: [StatsKind; 6] =
+        [StatsKind::Sum, StatsKind::Avg, StatsKind::Min, StatsKind::Max, StatsKind::Median, StatsKind::Stddev];
+
+    /// 根据名称获取统计项，忽略大小写，`all`表示全部统计项
+    pub(crate) fn from_name(name: &str) -> Option<&'static [StatsKind]> {
+        match name.to_ascii_lowercase().as_str() {
+            "sum" => Some(&[StatsKind::Sum]),
+            "avg" => Some(&[StatsKind::Avg]),
+            "min" => Some(&[StatsKind::Min]),
+            "max" => Some(&[StatsKind::Max]),
+            "median" => Some(&[StatsKind::Median]),
+            "stddev" => Some(&[StatsKind::Stddev]),
+            "all" => Some(&StatsKind::ALL),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            StatsKind::Sum => "sum",
+            StatsKind::Avg => "avg",
+            StatsKind::Min => "min",
+            StatsKind::Max => "max",
+            StatsKind::Median => "median",
+            StatsKind::Stddev => "stddev",
+        }
+    }
+}
+
+/// 计算统计结果，每个统计项输出一行，指定多个统计项时在结果前添加统计项名称。
+/// 没有任何数值时，除求和外的统计项无结果，不会输出。
+pub(crate) fn stats(mut nums: Vec<Num>, kinds: &[StatsKind]) -> Vec<String> {
+    if kinds.contains(&StatsKind::Median) {
+        nums.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    }
+    kinds
+        .iter()
+        .filter_map(|kind| {
+            let value = match kind {
+                StatsKind::Sum => Some(sum(&nums)),
+                StatsKind::Avg => avg(&nums).map(Num::from),
+                StatsKind::Min => nums.iter().copied().min_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal)),
+                StatsKind::Max => nums.iter().copied().max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal)),
+                StatsKind::Median => median(&nums),
+                StatsKind::Stddev => stddev(&nums).map(Num::from),
+            }?;
+            Some(if kinds.len() > 1 { format!("{} {}", kind.name(), value) } else { value.to_string() })
+        })
+        .collect()
+}
+
+/// 求和，全部为整数且未溢出时结果为整数，否则为浮点数
+fn sum(nums: &[Num]) -> Num {
+    nums.iter().fold(Num::Integer(0), |acc, num| match (acc, num) {
+        (Num::Integer(a), Num::Integer(b)) => {
+            a.checked_add(*b).map_or_else(|| Num::Float(a as Float + *b as Float), Num::Integer)
+        }
+        (a, b) => Num::Float(to_float(&a) + to_float(b)),
+    })
+}
+
+fn avg(nums: &[Num]) -> Option<Float> {
+    if nums.is_empty() { None } else { Some(to_float(&sum(nums)) / nums.len() as Float) }
+}
+
+/// 中位数，要求数值已经排序，数量为偶数时取中间两个数的平均值
+fn median(sorted: &[Num]) -> Option<Num> {
+    let mid = sorted.len() / 2;
+    match sorted.len() {
+        0 => None,
+        len if len % 2 == 1 => Some(sorted[mid]),
+        _ => Some(Num::Float((to_float(&sorted[mid - 1]) + to_float(&sorted[mid])) / 2.0)),
+    }
+}
+
+/// 总体标准差
+fn stddev(nums: &[Num]) -> Option<Float> {
+    let avg = avg(nums)?;
+    let variance = nums.iter().map(|num| (to_float(num) - avg).powi(2)).sum::<Float>() / nums.len() as Float;
+    Some(variance.sqrt())
+}
+
+fn to_float(num: &Num) -> Float {
+    match num {
+        Num::Integer(integer) => *integer as Float,
+        Num::Float(float) => *float,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Integer;
+
+    fn nums(values: &[&str]) -> Vec<Num> {
+        values.iter().map(|v| v.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_stats_single() {
+        assert_eq!(stats(nums(&["1", "2", "3", "4"]), &[StatsKind::Sum]), vec!["10"]);
+        assert_eq!(stats(nums(&["1", "2.5"]), &[StatsKind::Sum]), vec!["3.5"]);
+        assert_eq!(
+            stats(nums(&[&Integer::MAX.to_string(), "1"]), &[StatsKind::Sum]),
+            vec![(Integer::MAX as Float + 1.0).to_string()]
+        );
+        assert_eq!(stats(nums(&["1", "2"]), &[StatsKind::Avg]), vec!["1.5"]);
+        assert_eq!(stats(nums(&["3", "-1.5", "2"]), &[StatsKind::Min]), vec!["-1.5"]);
+        assert_eq!(stats(nums(&["3", "-1.5", "2"]), &[StatsKind::Max]), vec!["3"]);
+        assert_eq!(stats(nums(&["3", "1", "2"]), &[StatsKind::Median]), vec!["2"]);
+        assert_eq!(stats(nums(&["4", "1", "3", "2"]), &[StatsKind::Median]), vec!["2.5"]);
+        assert_eq!(stats(nums(&["2", "4", "4", "4", "5", "5", "7", "9"]), &[StatsKind::Stddev]), vec!["2"]);
+    }
+
+    #[test]
+    fn test_stats_empty() {
+        assert_eq!(stats(vec![], &[StatsKind::Sum]), vec!["0"]);
+        assert_eq!(stats(vec![], &[StatsKind::Avg]), Vec::<String>::new());
+        assert_eq!(stats(vec![], &StatsKind::ALL), vec!["sum 0"]);
+    }
+
+    #[test]
+    fn test_stats_all() {
+        assert_eq!(
+            stats(nums(&["1", "2", "3"]), &StatsKind::ALL),
+            vec!["sum 6", "avg 2", "min 1", "max 3", "median 2", &format!("stddev {}", (2.0 as Float / 3.0).sqrt()),]
+        );
+    }
+}
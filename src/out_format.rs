@@ -0,0 +1,133 @@
+use crate::encoding::TextEncoding;
+use crate::op::field::{quote_csv, split_csv};
+use std::borrow::Cow;
+use std::io;
+use std::io::Write;
+
+/// 结构化输出格式
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum OutFormat {
+    /// 所有数据组成一个JSON字符串数组
+    Json,
+    /// 每个数据输出为一行JSON字符串
+    Jsonl,
+    /// CSV，按照表头的列数将连续的数据组合为一行，每个数据为一个字段
+    Csv { header: Vec<String> },
+}
+
+impl OutFormat {
+    /// 表头按照CSV格式拆分为列名。
+    pub(crate) fn new_csv(header: &str) -> OutFormat {
+        OutFormat::Csv { header: split_csv(header).into_iter().map(Cow::into_owned).collect() }
+    }
+}
+
+/// 按照结构化输出格式写入数据，所有数据写入后需要调用`finish`补全剩余内容。
+pub(crate) struct Formatter {
+    format: OutFormat,
+    encoding: TextEncoding,
+    postfix: &'static str,
+    /// 已经写入的数据数量
+    count: usize,
+    /// CSV中尚未组成完整行的数据
+    row: Vec<String>,
+}
+
+impl Formatter {
+    pub(crate) fn new(format: OutFormat, encoding: TextEncoding, postfix: &'static str) -> Formatter {
+        Formatter { format, encoding, postfix, count: 0, row: Vec::new() }
+    }
+
+    pub(crate) fn write(&mut self, writer: &mut impl Write, item: &str) -> io::Result<()> {
+        let first = self.count == 0;
+        self.count += 1;
+        match &self.format {
+            OutFormat::Json => {
+                let text = format!("{}{}", if first { "[" } else { "," }, json_string(item));
+                self.encoding.write_record(writer, &text, "")
+            }
+            OutFormat::Jsonl => self.encoding.write_record(writer, &json_string(item), self.postfix),
+            OutFormat::Csv { header } => {
+                if first {
+                    self.encoding.write_record(writer, &csv_row(header), self.postfix)?;
+                }
+                self.row.push(item.to_string());
+                if self.row.len() == header.len() {
+                    self.encoding.write_record(writer, &csv_row(&self.row), self.postfix)?;
+                    self.row.clear();
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// 结束JSON数组；CSV没有数据时只输出表头，最后不完整的行使用空字段补齐。
+    pub(crate) fn finish(&mut self, writer: &mut impl Write) -> io::Result<()> {
+        match &self.format {
+            OutFormat::Json => self.encoding.write_record(writer, if self.count == 0 { "[]" } else { "]" }, "\n"),
+            OutFormat::Jsonl => Ok(()),
+            OutFormat::Csv { header } => {
+                if self.count == 0 {
+                    self.encoding.write_record(writer, &csv_row(header), self.postfix)?;
+                }
+                if !self.row.is_empty() {
+                    self.row.resize(header.len(), String::new());
+                    self.encoding.write_record(writer, &csv_row(&self.row), self.postfix)?;
+                    self.row.clear();
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn json_string(item: &str) -> String {
+    serde_json::to_string(item).expect("serialize string to json never fails")
+}
+
+fn csv_row(fields: &[String]) -> String {
+    let mut row = String::new();
+    for (idx, field) in fields.iter().enumerate() {
+        if idx > 0 {
+            row.push(',');
+        }
+        quote_csv(field, ",", &mut row);
+    }
+    row
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(format: OutFormat, items: &[&str]) -> String {
+        format_with(format, "\n", items)
+    }
+
+    fn format_with(format: OutFormat, postfix: &'static str, items: &[&str]) -> String {
+        let mut out = Vec::new();
+        let mut formatter = Formatter::new(format, TextEncoding::Utf8, postfix);
+        for item in items {
+            formatter.write(&mut out, item).unwrap();
+        }
+        formatter.finish(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(format(OutFormat::Json, &["a", r#"b"c\"#]), "[\"a\",\"b\\\"c\\\\\"]\n");
+        assert_eq!(format(OutFormat::Json, &[]), "[]\n");
+        assert_eq!(format_with(OutFormat::Json, "\0", &["a"]), "[\"a\"]\n");
+        assert_eq!(format_with(OutFormat::Jsonl, "\0", &["a"]), "\"a\"\0");
+        assert_eq!(format(OutFormat::Jsonl, &["a", "b\tc"]), "\"a\"\n\"b\\tc\"\n");
+        assert_eq!(format(OutFormat::Jsonl, &[]), "");
+        let csv = || OutFormat::new_csv(r#"name,"a,b""#);
+        assert_eq!(csv(), OutFormat::Csv { header: vec!["name".to_string(), "a,b".to_string()] });
+        assert_eq!(format(csv(), &["x", "1,2", "y\"", "z"]), "name,\"a,b\"\nx,\"1,2\"\n\"y\"\"\",z\n");
+        // 包含逗号的数据作为一个字段输出，不会再次拆分
+        assert_eq!(format(csv(), &["Smith, John", "42"]), "name,\"a,b\"\n\"Smith, John\",42\n");
+        assert_eq!(format(csv(), &["x"]), "name,\"a,b\"\nx,\n");
+        assert_eq!(format(csv(), &[]), "name,\"a,b\"\n");
+    }
+}
@@ -1,19 +1,45 @@
+use crate::cmd::CmdArg;
+use crate::compress::{Compress, CompressWriter};
+use crate::config::{encoding, is_nocase, record_sep, skip_err, Config};
+use crate::encoding::TextEncoding;
+use crate::file::AtomicFile;
+use crate::fmt::{fmt_args, FmtArg};
+use crate::op::build_regex;
+use crate::out_format::{Formatter, OutFormat};
 use crate::err::RpErr;
 use crate::pipe::Pipe;
+use crate::Integer;
 use cmd_help::CmdHelp;
-use std::fs::OpenOptions;
-use std::io::Write;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::{BufWriter, StdoutLock, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin};
 
-#[derive(Debug, Eq, PartialEq, CmdHelp)]
+#[derive(Debug, PartialEq, CmdHelp)]
 pub(crate) enum Output {
     /// :to out     输出到标准输出。
     ///             未指定元素输出时的默认输出。
     StdOut,
-    /// :to file    输出到文件。
-    ///             :to file <file>[ append][ lf|crlf]
+    /// :to file    输出到文件，支持压缩输出。
+    ///             :to file <file>[ append][ lf|crlf][ gzip|zstd|bzip2|xz|plain][ <level>]
     ///                 <file>  文件路径，必选。
     ///                 append  追加输出而不是覆盖，可选，如果未指定则覆盖源文件。
-    ///                 lf|crlf 指定换行符为'LF'或'CRLF'，可选，如果未指定则默认使用'LF'。
+    ///                         压缩输出时追加为新的压缩流，结果依然可以被完整解压。
+    ///                 lf|crlf 指定换行符为'LF'或'CRLF'，可选，如果未指定则使用全局记录分隔符，默认为'LF'。
+    ///                 gzip|zstd|bzip2|xz|plain
+    ///                         指定压缩格式，可选，plain表示不压缩。
+    ///                         未指定时根据文件扩展名推断：.gz、.zst、.bz2、.xz，其他扩展名不压缩。
+    ///                 <level> 压缩级别，可选，未指定时使用各压缩格式的默认级别。
+    ///                         未指定压缩格式时作用于根据文件扩展名推断的压缩格式，不压缩时不能指定。
+    ///                         gzip和xz为0到9，zstd为1到22，bzip2为1到9。
+    ///             覆盖输出时先写入同目录下的临时文件，全部输出成功后再替换目标文件，
+    ///             流水线失败时目标文件保持不变，因此可以输出到正在读取的文件。
+    ///             目标文件为符号链接时替换链接指向的文件，并保留原文件的权限和所有者；
+    ///             目标文件不是普通文件（例如设备或命名管道），或者无法创建临时文件时直接写入。
     ///             例如：
     ///                 :to file out.txt
     ///                 :to file out.txt append
@@ -21,7 +47,68 @@ pub(crate) enum Output {
     ///                 :to file out.txt lf
     ///                 :to file out.txt append crlf
     ///                 :to file out.txt append lf
-    File { file: String, append: bool, crlf: Option<bool> },
+    ///                 :to file out.txt.gz
+    ///                 :to file out.txt.gz 9
+    ///                 :to file out.txt zstd 19
+    File {
+        file: String,
+        append: bool,
+        crlf: Option<bool>,
+        compress: Option<Compress>,
+        level: Option<u32>,
+    },
+    /// :to split   按照键将数据拆分输出到多个文件。
+    ///             :to split <template>[ append][ lf|crlf][ gzip|zstd|bzip2|xz|plain][ <level>] by reg <regex>|by batch <n>
+    ///                 <template>  文件路径的格式化字符串，以{key}表示数据的键，必选。
+    ///                             文件所在的目录不存在时自动创建。
+    ///                 append|lf|crlf|gzip|zstd|bzip2|xz|plain|<level>
+    ///                             参考`:to file`，作用于每个文件。
+    ///                 by reg <regex>
+    ///                             使用正则表达式提取键，存在捕获组时取第一个捕获组，否则取整个匹配。
+    ///                             不匹配的数据被丢弃。
+    ///                 by batch <n>
+    ///                             每<n>个数据输出到一个文件，键为从0开始的批次序号，<n>必须为正整数。
+    ///             键为空、为'.'或'..'、或者包含路径分隔符时终止，如果全局配置了跳过错误，则丢弃此数据。
+    ///             同时打开的文件数量有上限，超过时关闭最久未写入的文件，再次写入此文件时追加写入。
+    ///             例如：
+    ///                 :to split 'out/{key}.txt' by reg '^(\w+)'
+    ///                 :to split 'part-{key:03}.txt' by batch 1000
+    ///                 :to split 'logs/{key}.log.gz' append by reg 'tenant=(\w+)'
+    Split {
+        template: String,
+        by: SplitBy,
+        append: bool,
+        crlf: Option<bool>,
+        compress: Option<Compress>,
+        level: Option<u32>,
+    },
+    /// :to cmd     输出到外部命令的标准输入。
+    ///             :to cmd[ lf|crlf] <program>[ <arg>][...]
+    ///                 lf|crlf     指定换行符为'LF'或'CRLF'，可选，如果未指定则使用全局记录分隔符，默认为'LF'。
+    ///                 <program>   程序名称或路径，必选，如果为'lf'或'crlf'，需要添加路径前缀，例如'./lf'。
+    ///                 <arg>       程序参数，可选，如果以':'开头，需要使用'\:'转义。
+    ///             直接启动程序而不经过shell，程序的标准输出和标准错误直接输出。
+    ///             程序提前退出时不再写入剩余数据。
+    ///             程序退出状态失败时，使用程序的退出码作为退出码。
+    ///             例如：
+    ///                 :to cmd sort -u
+    ///                 :to cmd crlf unix2dos
+    ///                 :to cmd mail -s report admin@example.com
+    Cmd { cmd: CmdArg, crlf: Option<bool> },
+    /// :to json    以JSON字符串数组的形式输出到标准输出，所有数据输出为一行。
+    /// :to jsonl   以JSON Lines的形式输出到标准输出，每个数据输出为一行JSON字符串。
+    /// :to csv     以CSV的形式输出到标准输出。
+    ///             :to csv <header>
+    ///                 <header>    CSV表头，必选，按照CSV格式拆分为列名。
+    ///             每个数据作为一个字段，连续的数据按照表头的列数组合为一行，例如`:split`拆分得到的字段，
+    ///             因此每条记录拆分得到的字段数量应该与表头的列数相同，最后不完整的行使用空字段补齐；
+    ///             字段包含逗号、双引号或换行符时使用双引号包裹。行之间使用全局记录分隔符分隔。
+    ///             例如：
+    ///                 :to json
+    ///                 :to jsonl
+    ///                 :to csv name,age
+    ///                 :to csv 'name,"city, country"'
+    Format(OutFormat),
     /// :to clip    输出到剪切板。
     ///             :to clip[ lf|crlf]
     ///                 lf|crlf 指定换行符为'LF'或'CRLF'，可选，如果未指定则默认使用'LF'。
@@ -31,50 +118,577 @@ pub(crate) enum Output {
     ///                 :to clip crlf
     #[cfg(windows)]
     Clip { crlf: Option<bool> },
+    /// :to <output>[ <output>][...]
+    ///             同时输出到多个目标，例如同时输出到标准输出和文件。
+    ///                 <output>    输出目标及其参数，即out、file、split、cmd、json、jsonl、csv或clip，
+    ///                             每个目标的参数互相独立。
+    ///                             cmd会使用剩余的全部参数，因此只能作为最后一个目标。
+    ///             任意目标写入失败时终止，cmd目标的命令提前退出时不影响其他目标。
+    ///             例如：
+    ///                 :to out file a.txt
+    ///                 :to out file a.txt file b.txt crlf
+    ///                 :to file all.txt append cmd sort -u
+    Tee(Vec<Output>),
 }
 
 impl Output {
     pub(crate) fn new_std_out() -> Self {
         Output::StdOut
     }
-    pub(crate) fn new_file(file: String, append: bool, crlf: Option<bool>) -> Self {
-        Output::File { file, append, crlf }
+    pub(crate) fn new_file(
+        file: String, append: bool, crlf: Option<bool>, compress: Option<Compress>, level: Option<u32>,
+    ) -> Self {
+        Output::File { file, append, crlf, compress, level }
+    }
+    pub(crate) fn new_split(
+        template: String, by: SplitBy, append: bool, crlf: Option<bool>, compress: Option<Compress>, level: Option<u32>,
+    ) -> Self {
+        Output::Split { template, by, append, crlf, compress, level }
+    }
+    pub(crate) fn new_cmd(cmd: CmdArg, crlf: Option<bool>) -> Self {
+        Output::Cmd { cmd, crlf }
+    }
+    pub(crate) fn new_format(format: OutFormat) -> Self {
+        Output::Format(format)
     }
     #[cfg(windows)]
     pub(crate) fn new_clip(crlf: Option<bool>) -> Self {
         Output::Clip { crlf }
     }
+    /// 只有一个输出目标时直接使用此目标。
+    pub(crate) fn new_tee(mut outputs: Vec<Output>) -> Self {
+        if outputs.len() == 1 { outputs.pop().unwrap() } else { Output::Tee(outputs) }
+    }
 
-    pub(crate) fn handle(self, pipe: Pipe) -> Result<(), RpErr> {
-        match self {
+    pub(crate) fn handle(self, pipe: Pipe, configs: &'static [Config]) -> Result<(), RpErr> {
+        let mut sink = self.open(configs)?;
+        for item in pipe {
+            sink.write(&item)?;
+        }
+        sink.finish()
+    }
+
+    fn open(self, configs: &'static [Config]) -> Result<Box<dyn Sink>, RpErr> {
+        let encoding = encoding(configs);
+        let record_sep = record_sep(configs);
+        Ok(match self {
             Output::StdOut => {
-                for item in pipe {
-                    println!("{item}");
-                }
-                Ok(())
+                Box::new(StdOutSink { writer: io::stdout().lock(), encoding, postfix: record_sep.postfix(None) })
             }
-            Output::File { file, append, crlf } => {
-                match OpenOptions::new().write(true).truncate(!append).append(append).create(true).open(&file) {
-                    Ok(mut writer) => {
-                        let postfix = if crlf.unwrap_or(false) { "\r\n" } else { "\n" };
-                        for item in pipe {
-                            write!(writer, "{item}{postfix}").map_err(|err| RpErr::WriteToFileErr {
-                                file: file.clone(),
-                                item: item.to_string(),
-                                err: err.to_string(),
-                            })?
-                        }
-                        Ok(())
+            Output::File { file, append, crlf, compress, level } => {
+                let direct = || {
+                    OpenOptions::new()
+                        .write(true)
+                        .truncate(!append)
+                        .append(append)
+                        .create(true)
+                        .open(&file)
+                        .map(FileTarget::File)
+                };
+                // 覆盖输出普通文件时原子写入，无法原子写入、追加或者输出到特殊文件时直接写入
+                let target = if !append && fs::metadata(&file).map_or(true, |meta| meta.is_file()) {
+                    AtomicFile::create(&file).map(FileTarget::Atomic).or_else(|_| direct())
+                } else {
+                    direct()
+                };
+                match target.and_then(|fout| Compress::encoder(compress, level, &file, fout)) {
+                    Ok(writer) => {
+                        Box::new(FileSink { file, writer, encoding, postfix: record_sep.postfix(crlf), backup: None })
                     }
-                    Err(err) => Err(RpErr::OpenFileErr { file, err: err.to_string() }),
+                    Err(err) => Err(RpErr::OpenFileErr { file, err: err.to_string() })?,
                 }
             }
+            Output::Split { template, by, append, crlf, compress, level } => Box::new(SplitSink {
+                template,
+                by: by.with_configs(configs)?,
+                append,
+                compress,
+                level,
+                encoding,
+                postfix: record_sep.postfix(crlf),
+                skip_err: skip_err(configs),
+                count: 0,
+                created: HashSet::new(),
+                writers: HashMap::new(),
+                tick: 0,
+            }),
+            Output::Cmd { cmd, crlf } => {
+                let mut child = cmd.spawn_writer()?;
+                let writer = BufWriter::new(child.stdin.take().expect("stdin of child is piped"));
+                Box::new(CmdSink { cmd, child, writer: Some(writer), encoding, postfix: record_sep.postfix(crlf) })
+            }
+            Output::Format(format) => Box::new(FormatSink {
+                writer: io::stdout().lock(),
+                formatter: Formatter::new(format, encoding, record_sep.postfix(None)),
+            }),
             #[cfg(windows)]
-            Output::Clip { crlf } => {
-                use itertools::Itertools;
-                let text = pipe.map(String::from).join(if crlf.unwrap_or(false) { "\r\n" } else { "\n" });
-                clipboard_win::set_clipboard_string(&text).map_err(|err| RpErr::WriteToClipboardErr(err.to_string()))
+            Output::Clip { crlf } => Box::new(ClipSink { items: Vec::new(), crlf }),
+            Output::Tee(outputs) => {
+                Box::new(TeeSink(outputs.into_iter().map(|output| output.open(configs)).collect::<Result<_, _>>()?))
+            }
+        })
+    }
+}
+
+/// 输出目标的写入器
+trait Sink {
+    /// 写入一个数据。
+    fn write(&mut self, item: &str) -> Result<(), RpErr>;
+
+    /// 完成输出，写入缓冲的数据并释放资源。
+    fn finish(self: Box<Self>) -> Result<(), RpErr>;
+}
+
+struct StdOutSink {
+    writer: StdoutLock<'static>,
+    encoding: TextEncoding,
+    postfix: &'static str,
+}
+
+impl Sink for StdOutSink {
+    fn write(&mut self, item: &str) -> Result<(), RpErr> {
+        self.encoding
+            .write_record(&mut self.writer, item, self.postfix)
+            .map_err(|err| RpErr::WriteToStdOutErr { item: item.to_string(), err: err.to_string() })
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<(), RpErr> {
+        self.writer.flush().map_err(|err| RpErr::WriteToStdOutErr { item: String::new(), err: err.to_string() })
+    }
+}
+
+/// 按照结构化格式输出到标准输出
+struct FormatSink {
+    writer: StdoutLock<'static>,
+    formatter: Formatter,
+}
+
+impl Sink for FormatSink {
+    fn write(&mut self, item: &str) -> Result<(), RpErr> {
+        self.formatter
+            .write(&mut self.writer, item)
+            .map_err(|err| RpErr::WriteToStdOutErr { item: item.to_string(), err: err.to_string() })
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<(), RpErr> {
+        self.formatter
+            .finish(&mut self.writer)
+            .and_then(|_| self.writer.flush())
+            .map_err(|err| RpErr::WriteToStdOutErr { item: String::new(), err: err.to_string() })
+    }
+}
+
+/// 原地编辑时将结果原子地写回文件，压缩格式与读取时相同，未指定时根据文件开头的魔数识别。
+/// 无法原子写入时，先读取全部结果，再备份并直接覆盖文件。
+pub(crate) fn write_in_place(
+    pipe: Pipe, file: String, compress: Option<Compress>, backup: Option<&'static str>, configs: &'static [Config],
+) -> Result<(), RpErr> {
+    let compress = match compress.map_or_else(|| Compress::detect_file(&file), Ok) {
+        Ok(compress) => compress,
+        Err(err) => return Err(RpErr::OpenFileErr { file, err: err.to_string() }),
+    };
+    let (items, target): (Box<dyn Iterator<Item = String>>, _) = match AtomicFile::create(&file) {
+        Ok(fout) => (Box::new(pipe), Ok(FileTarget::Atomic(fout))),
+        Err(_) => {
+            let items = pipe.collect::<Vec<_>>();
+            let target = backup
+                .map_or(Ok(()), |suffix| fs::copy(&file, format!("{file}{suffix}")).map(|_| ()))
+                .and_then(|_| File::create(&file))
+                .map(FileTarget::File);
+            (Box::new(items.into_iter()), target)
+        }
+    };
+    let writer = match target.and_then(|fout| Compress::encoder(Some(compress), None, &file, fout)) {
+        Ok(writer) => writer,
+        Err(err) => return Err(RpErr::OpenFileErr { file, err: err.to_string() }),
+    };
+    let mut sink = Box::new(FileSink {
+        file,
+        writer,
+        encoding: encoding(configs),
+        postfix: record_sep(configs).postfix(None),
+        backup,
+    });
+    for item in items {
+        sink.write(&item)?;
+    }
+    sink.finish()
+}
+
+/// 输出文件，原子写入的文件需要在完成输出后提交。
+enum FileTarget {
+    File(File),
+    Atomic(AtomicFile),
+}
+
+impl FileTarget {
+    fn commit(&mut self, backup: Option<&str>) -> io::Result<()> {
+        match self {
+            FileTarget::File(_) => Ok(()),
+            FileTarget::Atomic(atomic) => atomic.commit(backup),
+        }
+    }
+}
+
+impl Write for FileTarget {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            FileTarget::File(file) => file.write(buf),
+            FileTarget::Atomic(atomic) => atomic.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            FileTarget::File(file) => file.flush(),
+            FileTarget::Atomic(atomic) => atomic.flush(),
+        }
+    }
+}
+
+struct FileSink {
+    file: String,
+    writer: CompressWriter<FileTarget>,
+    encoding: TextEncoding,
+    postfix: &'static str,
+    /// 原子写入时备份文件的后缀
+    backup: Option<&'static str>,
+}
+
+impl Sink for FileSink {
+    fn write(&mut self, item: &str) -> Result<(), RpErr> {
+        self.encoding.write_record(&mut self.writer, item, self.postfix).map_err(|err| RpErr::WriteToFileErr {
+            file: self.file.clone(),
+            item: item.to_string(),
+            err: err.to_string(),
+        })
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<(), RpErr> {
+        let backup = self.backup;
+        self.writer.finish().and_then(|_| self.writer.get_mut().commit(backup)).map_err(|err| RpErr::WriteToFileErr {
+            file: self.file.clone(),
+            item: String::new(),
+            err: err.to_string(),
+        })
+    }
+}
+
+/// 拆分输出时数据的键
+#[derive(Debug)]
+pub(crate) enum SplitBy {
+    /// 正则表达式的第一个捕获组或者整个匹配
+    Reg(Regex),
+    /// 批次序号
+    Batch(usize),
+}
+
+impl PartialEq for SplitBy {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (SplitBy::Reg(l), SplitBy::Reg(r)) => l.as_str() == r.as_str(),
+            (SplitBy::Batch(l), SplitBy::Batch(r)) => l == r,
+            (_, _) => false,
+        }
+    }
+}
+
+impl SplitBy {
+    pub(crate) fn new_reg(reg: String) -> Result<SplitBy, RpErr> {
+        Ok(SplitBy::Reg(build_regex(&reg, false)?))
+    }
+
+    /// 全局配置了忽略大小写时重新编译正则
+    fn with_configs(self, configs: &[Config]) -> Result<SplitBy, RpErr> {
+        match self {
+            SplitBy::Reg(regex) if is_nocase(false, configs) => Ok(SplitBy::Reg(build_regex(regex.as_str(), true)?)),
+            by => Ok(by),
+        }
+    }
+}
+
+/// 按照键写入不同的文件，最近写入的文件的写入器被缓存，缓存数量超过上限时关闭最久未写入的文件。
+struct SplitSink {
+    template: String,
+    by: SplitBy,
+    append: bool,
+    compress: Option<Compress>,
+    level: Option<u32>,
+    encoding: TextEncoding,
+    postfix: &'static str,
+    /// 键无效时丢弃数据而不是终止
+    skip_err: bool,
+    /// 已经写入的数据数量
+    count: usize,
+    /// 已经创建的文件，再次打开时追加写入
+    created: HashSet<String>,
+    /// 缓存的写入器及其最近写入时间
+    writers: HashMap<String, (CompressWriter<File>, usize)>,
+    tick: usize,
+}
+
+impl SplitSink {
+    const MAX_OPEN_FILES: usize = 64;
+
+    fn writer(&mut self, file: String) -> Result<&mut CompressWriter<File>, RpErr> {
+        self.tick += 1;
+        if !self.writers.contains_key(&file) {
+            if self.writers.len() >= Self::MAX_OPEN_FILES
+                && let Some(lru) = self.writers.iter().min_by_key(|(_, (_, tick))| *tick).map(|(f, _)| f.clone())
+                && let Some((mut writer, _)) = self.writers.remove(&lru)
+            {
+                writer.finish().map_err(|err| RpErr::WriteToFileErr {
+                    file: lru,
+                    item: String::new(),
+                    err: err.to_string(),
+                })?;
+            }
+            let append = self.append || !self.created.insert(file.clone());
+            let writer = Path::new(&file)
+                .parent()
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|_| OpenOptions::new().write(true).truncate(!append).append(append).create(true).open(&file))
+                .and_then(|fout| Compress::encoder(self.compress, self.level, &file, fout))
+                .map_err(|err| RpErr::OpenFileErr { file: file.clone(), err: err.to_string() })?;
+            self.writers.insert(file.clone(), (writer, self.tick));
+        }
+        let (writer, tick) = self.writers.get_mut(&file).unwrap();
+        *tick = self.tick;
+        Ok(writer)
+    }
+}
+
+impl Sink for SplitSink {
+    fn write(&mut self, item: &str) -> Result<(), RpErr> {
+        let key = match &self.by {
+            SplitBy::Reg(regex) => match regex.captures(item) {
+                Some(caps) => {
+                    let key = caps.get(1).or_else(|| caps.get(0)).map_or("", |m| m.as_str());
+                    if !is_valid_key(key) {
+                        return if self.skip_err {
+                            Ok(())
+                        } else {
+                            Err(RpErr::InvalidSplitKeyErr { key: key.to_string(), item: item.to_string() })
+                        };
+                    }
+                    FmtArg::from(key)
+                }
+                None => return Ok(()), // 丢弃不匹配的数据
+            },
+            SplitBy::Batch(batch) => FmtArg::from((self.count / batch) as Integer),
+        };
+        self.count += 1;
+        let file = fmt_args(&self.template, &[("key", key)])?;
+        let (encoding, postfix) = (self.encoding, self.postfix);
+        let writer = self.writer(file.clone())?;
+        encoding.write_record(writer, item, postfix).map_err(|err| RpErr::WriteToFileErr {
+            file,
+            item: item.to_string(),
+            err: err.to_string(),
+        })
+    }
+
+    fn finish(self: Box<Self>) -> Result<(), RpErr> {
+        let mut res = Ok(());
+        for (file, (mut writer, _)) in self.writers {
+            res = res.and(writer.finish().map_err(|err| RpErr::WriteToFileErr {
+                file,
+                item: String::new(),
+                err: err.to_string(),
+            }));
+        }
+        res
+    }
+}
+
+/// 键作为文件名的一部分，不能为空、`.`或`..`，也不能包含路径分隔符，避免写入到模板指定的目录之外。
+fn is_valid_key(key: &str) -> bool {
+    !matches!(key, "" | "." | "..") && !key.contains(['/', '\\', '\0'])
+}
+
+/// 命令提前退出后不再写入剩余数据，最终以命令的退出状态为准。
+struct CmdSink {
+    cmd: CmdArg,
+    child: Child,
+    /// 命令的标准输入，命令提前退出后为空
+    writer: Option<BufWriter<ChildStdin>>,
+    encoding: TextEncoding,
+    postfix: &'static str,
+}
+
+impl CmdSink {
+    fn check(&mut self, res: io::Result<()>) -> Result<(), RpErr> {
+        match res {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => {
+                self.writer = None;
+                Ok(())
             }
+            Err(err) => Err(RpErr::RunCmdErr { cmd: self.cmd.to_string(), err: err.to_string() }),
         }
     }
 }
+
+impl Sink for CmdSink {
+    fn write(&mut self, item: &str) -> Result<(), RpErr> {
+        match &mut self.writer {
+            Some(writer) => {
+                let res = self.encoding.write_record(writer, item, self.postfix);
+                self.check(res)
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<(), RpErr> {
+        if let Some(mut writer) = self.writer.take() {
+            let res = writer.flush();
+            self.check(res)?;
+        } // 关闭标准输入，通知命令数据结束
+        self.cmd.wait_output(&mut self.child)
+    }
+}
+
+#[cfg(windows)]
+struct ClipSink {
+    items: Vec<String>,
+    crlf: Option<bool>,
+}
+
+#[cfg(windows)]
+impl Sink for ClipSink {
+    fn write(&mut self, item: &str) -> Result<(), RpErr> {
+        self.items.push(item.to_string());
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> Result<(), RpErr> {
+        let text = self.items.join(if self.crlf.unwrap_or(false) { "\r\n" } else { "\n" });
+        clipboard_win::set_clipboard_string(&text).map_err(|err| RpErr::WriteToClipboardErr(err.to_string()))
+    }
+}
+
+/// 同时写入多个输出目标，完成输出时依次完成所有目标，返回第一个错误。
+struct TeeSink(Vec<Box<dyn Sink>>);
+
+impl Sink for TeeSink {
+    fn write(&mut self, item: &str) -> Result<(), RpErr> {
+        for sink in &mut self.0 {
+            sink.write(item)?;
+        }
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> Result<(), RpErr> {
+        let mut res = Ok(());
+        for sink in self.0 {
+            res = res.and(sink.finish());
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::FileArg;
+    use crate::input::Input;
+    use std::io::Read;
+
+    fn split(template: String, by: SplitBy, append: bool, items: Vec<String>) {
+        let pipe = Pipe { iter: Box::new(items.into_iter()) };
+        Output::new_split(template, by, append, None, None, None).handle(pipe, &[]).unwrap();
+    }
+
+    #[test]
+    fn test_split() {
+        let root = std::env::temp_dir().join(format!("rp_output_test_split_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let template = format!("{}/{{key}}.txt", root.to_string_lossy());
+        let items = ["a 1", "b 2", "-", "a 3"].map(String::from).to_vec();
+        split(template.clone(), SplitBy::new_reg(r"^(\w+) ".to_string()).unwrap(), false, items.clone());
+        assert_eq!(fs::read_to_string(root.join("a.txt")).unwrap(), "a 1\na 3\n");
+        assert_eq!(fs::read_to_string(root.join("b.txt")).unwrap(), "b 2\n");
+        split(template.clone(), SplitBy::new_reg(r"^b".to_string()).unwrap(), true, items.clone());
+        assert_eq!(fs::read_to_string(root.join("b.txt")).unwrap(), "b 2\nb 2\n");
+        split(template.clone(), SplitBy::Batch(3), false, items);
+        assert_eq!(fs::read_to_string(root.join("0.txt")).unwrap(), "a 1\nb 2\n-\n");
+        assert_eq!(fs::read_to_string(root.join("1.txt")).unwrap(), "a 3\n");
+        // 超过打开文件数量上限时，被关闭的文件再次写入时追加写入
+        let count = SplitSink::MAX_OPEN_FILES + 1;
+        let items = (0..count * 2).map(|i| format!("k{}", i % count)).collect();
+        split(template, SplitBy::new_reg(".+".to_string()).unwrap(), false, items);
+        assert_eq!(fs::read_to_string(root.join("k0.txt")).unwrap(), "k0\nk0\n");
+        assert_eq!(
+            fs::read_to_string(root.join(format!("k{}.txt", count - 1))).unwrap(),
+            format!("k{0}\nk{0}\n", count - 1)
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_split_invalid_key() {
+        let root = std::env::temp_dir().join(format!("rp_output_test_split_invalid_key_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let template = format!("{}/out/{{key}}.txt", root.to_string_lossy());
+        let split = |items: &[&str], configs: &'static [Config]| {
+            let pipe = Pipe { iter: Box::new(items.iter().map(|item| item.to_string()).collect::<Vec<_>>().into_iter()) };
+            let by = SplitBy::new_reg(r"^([^ ]*) ".to_string()).unwrap();
+            Output::new_split(template.clone(), by, false, None, None, None).handle(pipe, configs)
+        };
+        for item in ["../x 1", "a/b 1", ". 1", " 1"] {
+            assert!(matches!(split(&[item], &[]), Err(RpErr::InvalidSplitKeyErr { .. })), "{item}");
+        }
+        split(&["../x 1", "a 2", "a/b 3", "a 4"], &[Config::SkipErr]).unwrap();
+        assert_eq!(fs::read_to_string(root.join("out/a.txt")).unwrap(), "a 2\na 4\n");
+        assert!(!root.join("x.txt").exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_write_in_place_compress() {
+        let root = std::env::temp_dir().join(format!("rp_output_test_in_place_compress_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        // 压缩格式与文件扩展名不一致时，按照读取时识别的格式写回
+        let (gzip, plain) = (root.join("gzip.txt"), root.join("plain.gz"));
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b"a\nb\n").unwrap();
+        fs::write(&gzip, encoder.finish().unwrap()).unwrap();
+        fs::write(&plain, "a\nb\n").unwrap();
+        for path in [&gzip, &plain] {
+            let file = path.to_string_lossy().into_owned();
+            let pipe = Input::new_file(FileArg::new(vec![file.clone()])).try_into(&[]).unwrap();
+            let pipe = Pipe { iter: Box::new(pipe.map(|item| item.to_uppercase())) };
+            write_in_place(pipe, file, None, None, &[]).unwrap();
+        }
+        let mut text = String::new();
+        flate2::read::MultiGzDecoder::new(File::open(&gzip).unwrap()).read_to_string(&mut text).unwrap();
+        assert_eq!(text, "A\nB\n");
+        assert_eq!(fs::read_to_string(&plain).unwrap(), "A\nB\n");
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_file_atomic() {
+        let root = std::env::temp_dir().join(format!("rp_output_test_file_atomic_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let file = root.join("out.txt").to_string_lossy().into_owned();
+        fs::write(&file, "old\n").unwrap();
+        // 未完成输出时目标文件保持不变，且不残留临时文件
+        let mut sink = Output::new_file(file.clone(), false, None, None, None).open(&[]).unwrap();
+        sink.write("new").unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "old\n");
+        drop(sink);
+        assert_eq!(fs::read_dir(&root).unwrap().count(), 1);
+        let pipe = Pipe { iter: Box::new(vec!["new".to_string()].into_iter()) };
+        Output::new_file(file.clone(), false, None, None, None).handle(pipe, &[]).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "new\n");
+        let pipe = Pipe { iter: Box::new(vec!["more".to_string()].into_iter()) };
+        Output::new_file(file.clone(), true, None, None, None).handle(pipe, &[]).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "new\nmore\n");
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -1,5 +1,6 @@
-use crate::condition::{Condition, Select, TextSelectMode};
+use crate::condition::{Condition, LiteralSelectMode, Select, TextSelectMode};
 use crate::err::RpErr;
+use crate::json::JsonPath;
 use crate::parse::args::parse_tag_nocase;
 use crate::parse::token::parse_num;
 use crate::parse::CondResult;
@@ -7,9 +8,37 @@ use nom::character::complete::usize;
 use nom::Parser;
 use std::iter::Peekable;
 
+/// 解析条件表达式，优先级从高到低依次为：not、and、or，支持使用`(`和`)`调整优先级。
 pub(in crate::parse::args) fn parse_cond(
     args: &mut Peekable<impl Iterator<Item = String>>, cmd: &'static str,
 ) -> CondResult {
+    let mut conds = vec![parse_cond_and(args, cmd)?];
+    while parse_tag_nocase(args, "or") {
+        conds.push(parse_cond_and(args, cmd)?);
+    }
+    Ok(Condition::new_or(conds))
+}
+
+fn parse_cond_and(args: &mut Peekable<impl Iterator<Item = String>>, cmd: &'static str) -> CondResult {
+    let mut conds = vec![parse_cond_not(args, cmd)?];
+    while parse_tag_nocase(args, "and") {
+        conds.push(parse_cond_not(args, cmd)?);
+    }
+    Ok(Condition::new_and(conds))
+}
+
+fn parse_cond_not(args: &mut Peekable<impl Iterator<Item = String>>, cmd: &'static str) -> CondResult {
+    if parse_tag_nocase(args, "not") {
+        Ok(parse_cond_not(args, cmd)?.not())
+    } else if parse_tag_nocase(args, "(") {
+        let cond = parse_cond(args, cmd)?;
+        if parse_tag_nocase(args, ")") { Ok(cond) } else { Err(RpErr::MissingArg { cmd, arg: ")" }) }
+    } else {
+        parse_cond_select(args, cmd)
+    }
+}
+
+fn parse_cond_select(args: &mut Peekable<impl Iterator<Item = String>>, cmd: &'static str) -> CondResult {
     let not = parse_tag_nocase(args, "not");
     match args.peek() {
         Some(arg) => match arg.to_ascii_lowercase().as_str() {
@@ -77,6 +106,28 @@ pub(in crate::parse::args) fn parse_cond(
                     Err(RpErr::MissingArg { cmd, arg: "reg regex" })
                 }
             }
+            "find" => {
+                args.next();
+                if let Some(regex) = args.next() {
+                    let nocase = parse_tag_nocase(args, "nocase");
+                    Select::new_reg_find(&regex, nocase).map(|regex| Condition::new(regex, not))
+                } else {
+                    Err(RpErr::MissingArg { cmd, arg: "find regex" })
+                }
+            }
+            "json" => {
+                args.next();
+                if let Some(path) = args.next() {
+                    let path = JsonPath::new(path, cmd)?;
+                    Ok(Condition::new(Select::new_json(path, parse_cond_select(args, cmd)?), not))
+                } else {
+                    Err(RpErr::MissingArg { cmd, arg: "json path" })
+                }
+            }
+            "contains" => parse_cond_literal(args, cmd, LiteralSelectMode::Contains, not),
+            "prefix" => parse_cond_literal(args, cmd, LiteralSelectMode::Prefix, not),
+            "suffix" => parse_cond_literal(args, cmd, LiteralSelectMode::Suffix, not),
+            "eq" => parse_cond_literal(args, cmd, LiteralSelectMode::Eq, not),
             "upper" => {
                 args.next();
                 Ok(Condition::new(Select::Text { mode: TextSelectMode::Upper }, not))
@@ -107,11 +158,23 @@ pub(in crate::parse::args) fn parse_cond(
     }
 }
 
+fn parse_cond_literal(
+    args: &mut Peekable<impl Iterator<Item = String>>, cmd: &'static str, mode: LiteralSelectMode, not: bool,
+) -> CondResult {
+    args.next();
+    if let Some(text) = args.next() {
+        let nocase = parse_tag_nocase(args, "nocase");
+        Ok(Condition::new(Select::new_literal(mode, text, nocase), not))
+    } else {
+        Err(RpErr::MissingArg { cmd, arg: "text" })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parse::args::build_args;
     use crate::Num;
+    use crate::parse::args::build_args;
 
     #[test]
     fn test_parse_cond_text_len_range() {
@@ -339,4 +402,95 @@ mod tests {
             Ok(Condition::new(Select::new_reg_match(r"\d+").unwrap(), true))
         );
     }
+
+    #[test]
+    fn test_parse_cond_and_or_not() {
+        let upper = || Condition::new(Select::Text { mode: TextSelectMode::Upper }, false);
+        let lower = || Condition::new(Select::Text { mode: TextSelectMode::Lower }, false);
+        let len3 = || Condition::new(Select::TextLenSpec { spec: 3 }, false);
+        assert_eq!(parse_cond(&mut build_args("upper and len 3"), ""), Ok(Condition::And(vec![upper(), len3()])));
+        assert_eq!(parse_cond(&mut build_args("upper OR len 3"), ""), Ok(Condition::Or(vec![upper(), len3()])));
+        assert_eq!(
+            parse_cond(&mut build_args("upper or lower and len 3"), ""),
+            Ok(Condition::Or(vec![upper(), Condition::And(vec![lower(), len3()])]))
+        );
+        assert_eq!(
+            parse_cond(&mut build_args("( upper or lower ) and len 3"), ""),
+            Ok(Condition::And(vec![Condition::Or(vec![upper(), lower()]), len3()]))
+        );
+        assert_eq!(
+            parse_cond(&mut build_args("not ( upper or lower ) or not len 3"), ""),
+            Ok(Condition::Or(vec![Condition::Or(vec![upper(), lower()]).not(), len3().not()]))
+        );
+        let mut args = build_args("not not upper :to out");
+        assert_eq!(parse_cond(&mut args, ""), Ok(upper().not().not()));
+        assert_eq!(args.next(), Some(":to".to_string()));
+        assert_eq!(parse_cond(&mut build_args("( upper"), ":take"), Err(RpErr::MissingArg { cmd: ":take", arg: ")" }));
+        assert_eq!(
+            parse_cond(&mut build_args("upper and"), ":take"),
+            Err(RpErr::MissingArg { cmd: ":take", arg: "condition" })
+        );
+    }
+
+    #[test]
+    fn test_parse_cond_literal() {
+        assert_eq!(
+            parse_cond(&mut build_args("contains abc"), ""),
+            Ok(Condition::new(Select::new_literal(LiteralSelectMode::Contains, "abc".to_string(), false), false))
+        );
+        assert_eq!(
+            parse_cond(&mut build_args("not prefix abc nocase"), ""),
+            Ok(Condition::new(Select::new_literal(LiteralSelectMode::Prefix, "abc".to_string(), true), true))
+        );
+        assert_eq!(
+            parse_cond(&mut build_args("suffix and and eq xyz"), ""),
+            Ok(Condition::And(vec![
+                Condition::new(Select::new_literal(LiteralSelectMode::Suffix, "and".to_string(), false), false),
+                Condition::new(Select::new_literal(LiteralSelectMode::Eq, "xyz".to_string(), false), false),
+            ]))
+        );
+        assert_eq!(parse_cond(&mut build_args("eq"), ":take"), Err(RpErr::MissingArg { cmd: ":take", arg: "text" }));
+    }
+
+    #[test]
+    fn test_parse_cond_reg_find() {
+        assert_eq!(
+            parse_cond(&mut build_args(r"find \d+ nocase"), ""),
+            Ok(Condition::new(Select::new_reg_find(r"\d+", true).unwrap(), false))
+        );
+        assert_eq!(
+            parse_cond(&mut build_args(r"not find \d+"), ""),
+            Ok(Condition::new(Select::new_reg_find(r"\d+", false).unwrap(), true))
+        );
+        assert!(matches!(parse_cond(&mut build_args("find ["), ""), Err(RpErr::ParseRegexErr { .. })));
+        assert_eq!(
+            parse_cond(&mut build_args("find"), ":take"),
+            Err(RpErr::MissingArg { cmd: ":take", arg: "find regex" })
+        );
+    }
+    #[test]
+    fn test_parse_cond_json() {
+        let path = |path: &str| JsonPath::new(path.to_string(), ":take").unwrap();
+        let num = Select::new_num_range(Some(Num::from(200)), Some(Num::from(299)));
+        assert_eq!(
+            parse_cond(&mut build_args("json .status num 200,299 and upper"), ":take"),
+            Ok(Condition::new_and(vec![
+                Condition::new(Select::new_json(path(".status"), num.yes()), false),
+                Select::Text { mode: TextSelectMode::Upper }.yes()
+            ]))
+        );
+        assert_eq!(
+            parse_cond(&mut build_args("not json .a[0] not empty"), ":take"),
+            Ok(Condition::new(
+                Select::new_json(path(".a[0]"), Select::Text { mode: TextSelectMode::Empty }.not()),
+                true
+            ))
+        );
+        assert_eq!(
+            parse_cond(&mut build_args("json"), ":take"),
+            Err(RpErr::MissingArg { cmd: ":take", arg: "json path" })
+        );
+        assert!(matches!(parse_cond(&mut build_args("json a num"), ":take"), Err(RpErr::ArgParseErr { .. })));
+        assert!(parse_cond(&mut build_args("json .a"), ":take").is_err());
+    }
 }
@@ -1,27 +1,78 @@
 use crate::config::Config;
+use crate::err::RpErr;
+use crate::parse::args::parse_arg;
 use crate::parse::{ConfigOptResult, ConfigsResult};
 use std::iter::Peekable;
 
 pub fn parse_configs(args: &mut Peekable<impl Iterator<Item = String>>) -> ConfigsResult {
     let mut configs = Vec::new();
-    while let Some(config) = parse_config(args.peek())? {
-        args.next();
+    while let Some(config) = parse_config(args)? {
         configs.push(config);
     }
     Ok(configs)
 }
 
-fn parse_config(arg: Option<&String>) -> ConfigOptResult {
-    Ok(match arg {
-        Some(arg) => match arg.as_str() {
-            "-h" | "--help" => Some(Config::Help),
-            "-V" | "--version" => Some(Config::Version),
-            "-v" | "--verbose" => Some(Config::Verbose),
-            "-d" | "--dry-run" => Some(Config::DryRun),
-            "-n" | "--nocase" => Some(Config::Nocase),
-            "-t" | "--token" => Some(Config::Token),
-            _ => None, // 遇到未知参数，停止解析
-        },
-        None => None,
-    })
+fn parse_config(args: &mut Peekable<impl Iterator<Item = String>>) -> ConfigOptResult {
+    let config = match args.peek().map(String::as_str) {
+        Some("-h" | "--help") => Config::Help,
+        Some("-V" | "--version") => Config::Version,
+        Some("-v" | "--verbose") => Config::Verbose,
+        Some("-d" | "--dry-run") => Config::DryRun,
+        Some("-n" | "--nocase") => Config::Nocase,
+        Some("-s" | "--skip-err") => Config::SkipErr,
+        Some("-t" | "--token") => Config::Token,
+        Some("-e" | "--encoding") => {
+            args.next();
+            match parse_arg(args) {
+                Some(encoding) => return Ok(Some(Config::Encoding(encoding.parse()?))),
+                None => Err(RpErr::MissingArg { cmd: "--encoding", arg: "<encoding>" })?,
+            }
+        }
+        Some("-r" | "--record-sep") => {
+            args.next();
+            match parse_arg(args) {
+                Some(record_sep) => return Ok(Some(Config::RecordSep(record_sep.parse()?))),
+                None => Err(RpErr::MissingArg { cmd: "--record-sep", arg: "<sep>" })?,
+            }
+        }
+        Some("-i" | "--in-place") => {
+            args.next();
+            let backup = args.next_if(|arg| !arg.starts_with(['-', ':']));
+            return Ok(Some(Config::InPlace(backup)));
+        }
+        _ => return Ok(None), // 遇到未知参数，停止解析
+    };
+    args.next();
+    Ok(Some(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{RecordSep, TextEncoding};
+    use crate::parse::args::build_args;
+
+    #[test]
+    fn test_parse_configs() {
+        let mut args = build_args("-n -e gbk --encoding utf16le -r nul --record-sep \\t -s :in");
+        assert_eq!(
+            parse_configs(&mut args),
+            Ok(vec![
+                Config::Nocase,
+                Config::Encoding(TextEncoding::Gbk),
+                Config::Encoding(TextEncoding::Utf16Le),
+                Config::RecordSep(RecordSep::Nul),
+                Config::RecordSep(RecordSep::Str("\t".to_string())),
+                Config::SkipErr
+            ])
+        );
+        assert_eq!(args.next(), Some(":in".to_string()));
+        assert!(parse_configs(&mut build_args("-e")).is_err());
+        assert!(parse_configs(&mut build_args("-e ascii")).is_err());
+        assert!(parse_configs(&mut build_args("-r")).is_err());
+        assert_eq!(parse_configs(&mut build_args("-i -n")), Ok(vec![Config::InPlace(None), Config::Nocase]));
+        let mut args = build_args("--in-place .bak :file a.txt");
+        assert_eq!(parse_configs(&mut args), Ok(vec![Config::InPlace(Some(".bak".to_string()))]));
+        assert_eq!(args.next(), Some(":file".to_string()));
+    }
 }
@@ -1,7 +1,8 @@
+use crate::cmd::CmdArg;
 use crate::err::RpErr;
 use crate::input::Input;
 use crate::parse::args::{parse_arg, parse_arg1, parse_opt_arg, parse_positive_usize};
-use crate::parse::InputResult;
+use crate::parse::{parse_file_arg, InputResult};
 use std::iter::Peekable;
 
 pub(in crate::parse::args) fn parse_input(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResult {
@@ -11,6 +12,7 @@ pub(in crate::parse::args) fn parse_input(args: &mut Peekable<impl Iterator<Item
             match lower_input.as_str() {
                 ":in" => parse_std_in(args),
                 ":file" => parse_file(args),
+                ":cmd" => parse_cmd(args),
                 #[cfg(windows)]
                 ":clip" => parse_clip(args),
                 ":of" => parse_of(args),
@@ -30,7 +32,12 @@ fn parse_std_in(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResul
 
 fn parse_file(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResult {
     args.next(); // 消耗命令文本
-    Ok(Input::new_file(parse_arg1(args, ":file", "file")?))
+    Ok(Input::new_file(parse_file_arg(parse_arg1(args, ":file", "file")?, "file")?))
+}
+
+fn parse_cmd(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResult {
+    args.next(); // 消耗命令文本
+    Ok(Input::new_cmd(CmdArg::new(parse_arg1(args, ":cmd", "program")?)))
 }
 
 #[cfg(windows)]
@@ -71,8 +78,11 @@ fn parse_repeat(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResul
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parse::args::build_args;
     use crate::Integer;
+    use crate::compress::Compress;
+    use crate::file::FileArg;
+    use crate::parse::args::build_args;
+    use glob::Pattern;
 
     #[test]
     fn test_parse_std_in() {
@@ -92,12 +102,12 @@ mod tests {
     #[test]
     fn test_parse_file() {
         let mut args = build_args(":file name");
-        assert_eq!(Ok(Input::new_file(vec!["name".to_string()])), parse_input(&mut args));
+        assert_eq!(Ok(Input::new_file(FileArg::new(vec!["name".to_string()]))), parse_input(&mut args));
         assert!(args.next().is_none());
 
         let mut args = build_args(":file name1 name2 \\] :123");
         assert_eq!(
-            Ok(Input::new_file(vec!["name1".to_string(), "name2".to_string(), "\\]".to_string()])),
+            Ok(Input::new_file(FileArg::new(vec!["name1".to_string(), "name2".to_string(), "\\]".to_string()]))),
             parse_input(&mut args)
         );
         assert_eq!(Some(":123".to_string()), args.next());
@@ -106,11 +116,58 @@ mod tests {
         assert_eq!(Err(RpErr::MissingArg { cmd: ":file", arg: "file" }), parse_input(&mut args));
         assert!(args.next().is_none());
 
+        let mut args = build_args(":file a.log.gz b.log.zst xz :123");
+        assert_eq!(
+            Ok(Input::new_file(FileArg {
+                compress: Some(Compress::Xz),
+                ..FileArg::new(vec!["a.log.gz".to_string(), "b.log.zst".to_string()])
+            })),
+            parse_input(&mut args)
+        );
+        assert_eq!(Some(":123".to_string()), args.next());
+
+        let mut args = build_args(":file bzip2");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":file", arg: "file" }), parse_input(&mut args));
+
+        let mut args = build_args(":file logs *.txt recursive include *.log exclude *.bak.log include *.out");
+        assert_eq!(
+            Ok(Input::new_file(FileArg {
+                recursive: true,
+                include: vec![Pattern::new("*.log").unwrap(), Pattern::new("*.out").unwrap()],
+                exclude: vec![Pattern::new("*.bak.log").unwrap()],
+                ..FileArg::new(vec!["logs".to_string(), "*.txt".to_string()])
+            })),
+            parse_input(&mut args)
+        );
+
+        let mut args = build_args(":file logs include");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":file", arg: "<glob>" }), parse_input(&mut args));
+        let mut args = build_args(":file logs recursive other");
+        assert!(parse_input(&mut args).is_err());
+        let mut args = build_args(":file logs exclude ***");
+        assert!(parse_input(&mut args).is_err());
+
         let mut args = build_args(":file [ ]");
-        assert_eq!(Ok(Input::new_file(vec!["[".to_string(), "]".to_string()])), parse_input(&mut args));
+        assert_eq!(Ok(Input::new_file(FileArg::new(vec!["[".to_string(), "]".to_string()]))), parse_input(&mut args));
         assert!(args.next().is_none());
     }
 
+    #[test]
+    fn test_parse_cmd() {
+        let mut args = build_args(":cmd git log --oneline \\:x :upper");
+        assert_eq!(
+            Ok(Input::new_cmd(CmdArg {
+                program: "git".to_string(),
+                args: vec!["log".to_string(), "--oneline".to_string(), ":x".to_string()]
+            })),
+            parse_input(&mut args)
+        );
+        assert_eq!(Some(":upper".to_string()), args.next());
+
+        let mut args = build_args(":cmd");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":cmd", arg: "program" }), parse_input(&mut args));
+    }
+
     #[test]
     #[cfg(windows)]
     fn test_parse_clip() {
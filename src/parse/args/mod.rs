@@ -1,10 +1,13 @@
+use crate::compress::Compress;
 use crate::err::RpErr;
 use crate::input::Input;
 use crate::op::Op;
+use crate::out_format::OutFormat;
 use crate::output::Output;
 use crate::parse::args::input::parse_input;
 use crate::parse::args::op::parse_ops;
 use crate::parse::args::output::parse_output;
+use crate::parse::FileInfo;
 use std::iter::Peekable;
 use std::str::FromStr;
 
@@ -99,11 +102,11 @@ fn parse_as<T: FromStr>(args: &mut Peekable<impl Iterator<Item = String>>) -> Op
     }
 }
 
-/// 解析一般的文件信息`file[ append][ <crlf|lf>]`
+/// 解析一般的文件信息`file[ append][ <crlf|lf>][ <compress>][ <level>]`
 /// 如果`optional`为`false`，则file参数必须非命令格式
 fn parse_general_file_info(
-    args: &mut Peekable<impl Iterator<Item = String>>, optional: bool,
-) -> Option<(String, bool, Option<bool>)> {
+    args: &mut Peekable<impl Iterator<Item = String>>, cmd: &'static str, optional: bool,
+) -> Result<Option<FileInfo>, RpErr> {
     if let Some(file) = if optional { parse_opt_arg(args) } else { parse_arg(args) } {
         let (append, crlf) = if let Some(append_or_postfix) = args.peek() {
             if append_or_postfix.eq_ignore_ascii_case("append") {
@@ -133,9 +136,47 @@ fn parse_general_file_info(
         } else {
             (false, None)
         };
-        Some((file, append, crlf))
+        let (compress, level) = parse_file_compress(args, cmd, &file)?;
+        Ok(Some((file, append, crlf, compress, level)))
     } else {
-        None
+        Ok(None)
+    }
+}
+
+/// 解析可选的结构化输出格式`json|jsonl|csv <header>`
+fn parse_out_format(
+    args: &mut Peekable<impl Iterator<Item = String>>, cmd: &'static str,
+) -> Result<Option<OutFormat>, RpErr> {
+    if parse_tag_nocase(args, "json") {
+        Ok(Some(OutFormat::Json))
+    } else if parse_tag_nocase(args, "jsonl") {
+        Ok(Some(OutFormat::Jsonl))
+    } else if parse_tag_nocase(args, "csv") {
+        let header = parse_arg(args).ok_or(RpErr::MissingArg { cmd, arg: "header" })?;
+        Ok(Some(OutFormat::new_csv(&header)))
+    } else {
+        Ok(None)
+    }
+}
+
+/// 解析可选的压缩格式和压缩级别`[gzip|zstd|bzip2|xz|plain][ <level>]`，
+/// 未指定压缩格式时，根据文件扩展名推断的压缩格式校验压缩级别。
+fn parse_file_compress(
+    args: &mut Peekable<impl Iterator<Item = String>>, cmd: &'static str, file: &str,
+) -> Result<(Option<Compress>, Option<u32>), RpErr> {
+    let compress = args.peek().and_then(|arg| Compress::from_name(arg));
+    if compress.is_some() {
+        args.next(); // 消耗压缩格式
+    }
+    let level_compress = compress.unwrap_or_else(|| Compress::infer(file));
+    match parse_as::<u32>(args) {
+        Some(level) if !level_compress.is_valid_level(level) => Err(RpErr::ArgParseErr {
+            cmd,
+            arg: "level",
+            arg_value: level.to_string(),
+            error: format!("invalid level for {level_compress:?}"),
+        }),
+        level => Ok((compress, level)),
     }
 }
 
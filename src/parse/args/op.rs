@@ -1,13 +1,18 @@
 use crate::err::RpErr;
+use crate::json::JsonPath;
+use crate::op::field::{FieldArg, FieldSep};
+use crate::op::split::SplitArg;
+use crate::op::stats::StatsKind;
 use crate::op::trim::{TrimArg, TrimPos};
 use crate::op::{CaseArg, JoinInfo, Op, PeekArg, SortBy, TakeDropMode};
 use crate::parse::args::condition::parse_cond;
 use crate::parse::args::{
-    parse_arg, parse_as, parse_general_file_info, parse_opt_arg, parse_positive_usize, parse_tag_nocase, parse_usize,
+    parse_arg, parse_arg1, parse_as, parse_general_file_info, parse_opt_arg, parse_out_format, parse_positive_usize, parse_tag_nocase, parse_usize,
 };
-use crate::parse::token::parse_usize_range;
-use crate::parse::{OpOptResult, OpResult, OpsResult};
-use crate::{Float, Integer};
+use crate::parse::token::op::parse_start_step_in_number;
+use crate::parse::token::{parse_index_range, parse_usize_range};
+use crate::parse::{parse_exec_arg, OpOptResult, OpResult, OpsResult};
+use crate::{Float, Integer, Num};
 use std::iter::Peekable;
 
 pub(in crate::parse::args) fn parse_ops(args: &mut Peekable<impl Iterator<Item = String>>) -> OpsResult {
@@ -28,6 +33,7 @@ fn parse_op(args: &mut Peekable<impl Iterator<Item = String>>) -> OpOptResult {
                 ":upper" => Some(parse_case(CaseArg::Upper, args)?),
                 ":case" => Some(parse_case(CaseArg::Switch, args)?),
                 ":replace" => Some(parse_replace(args)?),
+                ":replacer" => Some(parse_regex_replace(args)?),
                 ":trim" => Some(parse_trim(TrimPos::Both, false, args)?),
                 ":ltrim" => Some(parse_trim(TrimPos::Head, false, args)?),
                 ":rtrim" => Some(parse_trim(TrimPos::Tail, false, args)?),
@@ -37,6 +43,12 @@ fn parse_op(args: &mut Peekable<impl Iterator<Item = String>>) -> OpOptResult {
                 ":trimr" => Some(parse_trim_regex(":trimr", TrimPos::Both, args)?),
                 ":ltrimr" => Some(parse_trim_regex(":ltrimr", TrimPos::Head, args)?),
                 ":rtrimr" => Some(parse_trim_regex(":rtrimr", TrimPos::Tail, args)?),
+                ":field" => Some(parse_field(args)?),
+                ":json" => Some(parse_json(args)?),
+                ":number" => Some(parse_number(args)?),
+                ":fmt" => Some(parse_fmt(args)?),
+                ":tag" => Some(parse_tag(args)?),
+                ":exec" => Some(parse_exec(args)?),
                 ":limit" => Some(parse_limit(args)?),
                 ":skip" => Some(parse_skip(args)?),
                 ":slice" => Some(parse_slice(args)?),
@@ -45,6 +57,9 @@ fn parse_op(args: &mut Peekable<impl Iterator<Item = String>>) -> OpOptResult {
                 ":drop" => Some(parse_drop_or_drop_while(args)?),
                 ":take" => Some(parse_take_or_take_while(args)?),
                 ":count" => Some(parse_count(args)?),
+                ":stats" => Some(parse_stats(args)?),
+                ":freq" => Some(parse_freq(args)?),
+                ":split" => Some(parse_split(args)?),
                 ":sort" => Some(parse_sort(args)?),
                 _ => None,
             })
@@ -55,8 +70,10 @@ fn parse_op(args: &mut Peekable<impl Iterator<Item = String>>) -> OpOptResult {
 
 fn parse_peek(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
     args.next();
-    if let Some((file, append, crlf)) = parse_general_file_info(args, true) {
-        Ok(Op::Peek(PeekArg::File { file, append, crlf }))
+    if let Some(format) = parse_out_format(args, ":peek")? {
+        Ok(Op::Peek(PeekArg::Format(format)))
+    } else if let Some((file, append, crlf, compress, level)) = parse_general_file_info(args, ":peek", true)? {
+        Ok(Op::Peek(PeekArg::File { file, append, crlf, compress, level }))
     } else {
         Ok(Op::Peek(PeekArg::StdOut))
     }
@@ -84,6 +101,23 @@ fn parse_replace(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult
     }
 }
 
+fn parse_regex_replace(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
+    args.next();
+    // 被替换正则必选，直接消耗
+    if let Some(regex) = parse_arg(args) {
+        // 替换目标字符串必选，直接消耗
+        if let Some(to) = parse_arg(args) {
+            let count_opt = parse_positive_usize(args);
+            let nocase = parse_tag_nocase(args, "nocase");
+            Op::new_regex_replace(regex, to, count_opt, nocase)
+        } else {
+            Err(RpErr::MissingArg { cmd: ":replacer", arg: "to" })
+        }
+    } else {
+        Err(RpErr::MissingArg { cmd: ":replacer", arg: "regex" })
+    }
+}
+
 fn parse_trim(pos: TrimPos, char_mode: bool, args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
     args.next();
     let pattern = parse_opt_arg(args);
@@ -104,6 +138,77 @@ fn parse_trim_regex(cmd: &'static str, pos: TrimPos, args: &mut Peekable<impl It
     }
 }
 
+fn parse_field(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
+    args.next();
+    let mut ranges = vec![];
+    while let Some(arg) = args.peek()
+        && let Ok((remaining, range)) = parse_index_range(arg)
+        && remaining.is_empty()
+    {
+        args.next();
+        ranges.push(range);
+    }
+    if ranges.is_empty() {
+        return Err(RpErr::MissingArg { cmd: ":field", arg: "index" });
+    }
+    let sep = if parse_tag_nocase(args, "sep") {
+        FieldSep::Str(parse_arg(args).ok_or(RpErr::MissingArg { cmd: ":field", arg: "delimiter" })?)
+    } else if parse_tag_nocase(args, "csv") {
+        FieldSep::Csv
+    } else if parse_tag_nocase(args, "tsv") {
+        FieldSep::Tsv
+    } else {
+        parse_tag_nocase(args, "ws");
+        FieldSep::Ws
+    };
+    let join = if parse_tag_nocase(args, "join") {
+        Some(parse_arg(args).ok_or(RpErr::MissingArg { cmd: ":field", arg: "out" })?)
+    } else {
+        None
+    };
+    Ok(Op::Field(FieldArg::new(ranges, sep, join)))
+}
+
+fn parse_json(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
+    args.next();
+    let path = parse_arg(args).ok_or(RpErr::MissingArg { cmd: ":json", arg: "path" })?;
+    Ok(Op::new_json(JsonPath::new(path, ":json")?))
+}
+
+fn parse_number(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
+    args.next();
+    let (start, step) = if let Some(arg) = args.peek()
+        && let Ok((remaining, start_step)) = parse_start_step_in_number(arg)
+        && remaining.is_empty()
+    {
+        args.next();
+        start_step
+    } else {
+        (1, 1)
+    };
+    Ok(Op::new_number(start, step, parse_opt_arg(args)))
+}
+
+fn parse_fmt(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
+    args.next();
+    // 格式化字符串必选，直接消耗
+    if let Some(fmt) = parse_arg(args) {
+        Ok(Op::Fmt { fmt })
+    } else {
+        Err(RpErr::MissingArg { cmd: ":fmt", arg: "template" })
+    }
+}
+
+fn parse_tag(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
+    args.next();
+    Ok(Op::new_tag(parse_opt_arg(args)))
+}
+
+fn parse_exec(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
+    args.next();
+    Ok(Op::new_exec(parse_exec_arg(parse_arg1(args, ":exec", "program")?, "program")?))
+}
+
 fn parse_limit(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
     args.next();
     let count = parse_usize(":limit", "count", args)?;
@@ -189,6 +294,51 @@ fn parse_count(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
     Ok(Op::Count)
 }
 
+fn parse_stats(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
+    args.next();
+    let mut kinds = vec![];
+    while let Some(arg) = args.peek()
+        && let Some(new_kinds) = StatsKind::from_name(arg)
+    {
+        args.next();
+        kinds.extend_from_slice(new_kinds);
+    }
+    Ok(Op::new_stats(kinds, parse_as::<Num>(args)))
+}
+
+fn parse_freq(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
+    args.next();
+    let nocase = parse_tag_nocase(args, "nocase");
+    let desc = if parse_tag_nocase(args, "desc") {
+        Some(true)
+    } else if parse_tag_nocase(args, "asc") {
+        Some(false)
+    } else {
+        None
+    };
+    Ok(Op::new_freq(nocase, desc, parse_opt_arg(args)))
+}
+
+fn parse_split(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
+    args.next();
+    if parse_tag_nocase(args, "reg") {
+        // 指定`reg`时分隔正则必选，直接消耗
+        if let Some(regex) = parse_arg(args) {
+            let nocase = parse_tag_nocase(args, "nocase");
+            let keep_empty = parse_tag_nocase(args, "keep-empty");
+            Ok(Op::Split(SplitArg::new_regex(regex, nocase, keep_empty)?))
+        } else {
+            Err(RpErr::MissingArg { cmd: ":split", arg: "regex" })
+        }
+    } else if let Some(delimiter) = parse_opt_arg(args) {
+        let nocase = parse_tag_nocase(args, "nocase");
+        let keep_empty = parse_tag_nocase(args, "keep-empty");
+        Ok(Op::Split(SplitArg::new_str(delimiter, nocase, keep_empty)))
+    } else {
+        Ok(Op::Split(SplitArg::new_blank()))
+    }
+}
+
 fn parse_sort(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
     args.next();
     let sort_by = if let Some(sort_by) = args.peek() {
@@ -229,7 +379,10 @@ fn parse_sort(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cmd::CmdArg;
     use crate::op::CaseArg;
+    use crate::op::exec::{ExecArg, ExecMode};
+    use crate::out_format::OutFormat;
     use crate::parse::args::build_args;
 
     #[test]
@@ -248,6 +401,15 @@ mod tests {
         let mut args = build_args(":uniq nocase");
         assert_eq!(Ok(Some(Op::Uniq { nocase: true })), parse_op(&mut args));
         assert!(args.next().is_none());
+
+        let mut args = build_args(":peek jsonl :upper");
+        assert_eq!(Ok(Some(Op::Peek(PeekArg::Format(OutFormat::Jsonl)))), parse_op(&mut args));
+        assert_eq!(Some(":upper".to_string()), args.next());
+
+        let mut args = build_args(":peek csv a,b");
+        assert_eq!(Ok(Some(Op::Peek(PeekArg::Format(OutFormat::new_csv("a,b"))))), parse_op(&mut args));
+        assert!(args.next().is_none());
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":peek", arg: "header" }), parse_op(&mut build_args(":peek csv")));
     }
 
     #[test]
@@ -296,6 +458,34 @@ mod tests {
         assert!(args.next().is_none());
     }
 
+    #[test]
+    fn test_parse_regex_replace() {
+        let mut args = build_args(r":replacer (\d+) <$1>");
+        assert_eq!(
+            Ok(Some(Op::new_regex_replace(r"(\d+)".to_string(), "<$1>".to_string(), None, false).unwrap())),
+            parse_op(&mut args)
+        );
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":replacer abc xyz 10 nocase");
+        assert_eq!(
+            Ok(Some(Op::new_regex_replace("abc".to_string(), "xyz".to_string(), Some(10), true).unwrap())),
+            parse_op(&mut args)
+        );
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":replacer [ xyz");
+        assert!(matches!(parse_op(&mut args), Err(RpErr::ParseRegexErr { .. })));
+
+        let mut args = build_args(":replacer abc");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":replacer", arg: "to" }), parse_op(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":replacer");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":replacer", arg: "regex" }), parse_op(&mut args));
+        assert!(args.next().is_none());
+    }
+
     #[test]
     fn test_parse_trim() {
         // trim
@@ -420,6 +610,151 @@ mod tests {
         assert!(parse_op(&mut build_args(":rtrimr ")).is_err());
     }
 
+    #[test]
+    fn test_parse_field() {
+        let mut args = build_args(":field 0 -1 2,3 -2, ,-3");
+        assert_eq!(
+            Ok(Some(Op::Field(FieldArg::new(
+                vec![(Some(0), Some(0)), (Some(-1), Some(-1)), (Some(2), Some(3)), (Some(-2), None), (None, Some(-3))],
+                FieldSep::Ws,
+                None
+            )))),
+            parse_op(&mut args)
+        );
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":field 1 sep , join ;");
+        assert_eq!(
+            Ok(Some(Op::Field(FieldArg::new(
+                vec![(Some(1), Some(1))],
+                FieldSep::Str(",".to_string()),
+                Some(";".to_string())
+            )))),
+            parse_op(&mut args)
+        );
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":field 1 csv :count");
+        assert_eq!(
+            Ok(Some(Op::Field(FieldArg::new(vec![(Some(1), Some(1))], FieldSep::Csv, None)))),
+            parse_op(&mut args)
+        );
+        assert_eq!(Some(":count".to_string()), args.next());
+
+        let mut args = build_args(":field 1 tsv");
+        assert_eq!(
+            Ok(Some(Op::Field(FieldArg::new(vec![(Some(1), Some(1))], FieldSep::Tsv, None)))),
+            parse_op(&mut args)
+        );
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":field 1 ws join ,");
+        assert_eq!(
+            Ok(Some(Op::Field(FieldArg::new(vec![(Some(1), Some(1))], FieldSep::Ws, Some(",".to_string()))))),
+            parse_op(&mut args)
+        );
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":field csv");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":field", arg: "index" }), parse_op(&mut args));
+
+        let mut args = build_args(":field 1 sep");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":field", arg: "delimiter" }), parse_op(&mut args));
+    }
+
+    #[test]
+    fn test_parse_number() {
+        let mut args = build_args(":number");
+        assert_eq!(Ok(Some(Op::new_number(1, 1, None))), parse_op(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":number :count");
+        assert_eq!(Ok(Some(Op::new_number(1, 1, None))), parse_op(&mut args));
+        assert_eq!(Some(":count".to_string()), args.next());
+
+        let mut args = build_args(":number 10,-2");
+        assert_eq!(Ok(Some(Op::new_number(10, -2, None))), parse_op(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":number 0 {i}:{v}");
+        assert_eq!(Ok(Some(Op::new_number(0, 1, Some("{i}:{v}".to_string())))), parse_op(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":number #{i}");
+        assert_eq!(Ok(Some(Op::new_number(1, 1, Some("#{i}".to_string())))), parse_op(&mut args));
+        assert!(args.next().is_none());
+    }
+
+    #[test]
+    fn test_parse_fmt() {
+        let mut args = build_args(":fmt [{v}]");
+        assert_eq!(Ok(Some(Op::Fmt { fmt: "[{v}]".to_string() })), parse_op(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":fmt");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":fmt", arg: "template" }), parse_op(&mut args));
+    }
+
+    #[test]
+    fn test_parse_exec() {
+        let mut args = build_args(":exec echo {v} :upper");
+        assert_eq!(
+            Ok(Some(Op::new_exec(ExecArg::new(CmdArg::new(vec!["echo".to_string(), "{v}".to_string()]))))),
+            parse_op(&mut args)
+        );
+        assert_eq!(Some(":upper".to_string()), args.next());
+
+        let mut args = build_args(":exec FILTER batch 10 parallel 4 test -f");
+        assert_eq!(
+            Ok(Some(Op::new_exec(ExecArg {
+                mode: ExecMode::Filter,
+                batch: 10,
+                parallel: 4,
+                ..ExecArg::new(CmdArg::new(vec!["test".to_string(), "-f".to_string()]))
+            }))),
+            parse_op(&mut args)
+        );
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":exec peek ./peek");
+        assert_eq!(
+            Ok(Some(Op::new_exec(ExecArg {
+                mode: ExecMode::Peek,
+                ..ExecArg::new(CmdArg::new(vec!["./peek".to_string()]))
+            }))),
+            parse_op(&mut args)
+        );
+
+        let mut args = build_args(":exec");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":exec", arg: "program" }), parse_op(&mut args));
+        let mut args = build_args(":exec peek");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":exec", arg: "program" }), parse_op(&mut args));
+        let mut args = build_args(":exec batch 0 echo");
+        assert!(parse_op(&mut args).is_err());
+        let mut args = build_args(":exec parallel");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":exec", arg: "<parallel>" }), parse_op(&mut args));
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let mut args = build_args(":json .a.b[0] :upper");
+        assert_eq!(Ok(Some(Op::new_json(JsonPath::new(".a.b[0]".to_string(), ":json").unwrap()))), parse_op(&mut args));
+        assert_eq!(Some(":upper".to_string()), args.next());
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":json", arg: "path" }), parse_op(&mut build_args(":json")));
+        assert!(parse_op(&mut build_args(":json a")).is_err());
+    }
+
+    #[test]
+    fn test_parse_tag() {
+        let mut args = build_args(":tag :upper");
+        assert_eq!(Ok(Some(Op::Tag { fmt: "{file}:{line}: ".to_string() })), parse_op(&mut args));
+        assert_eq!(Some(":upper".to_string()), args.next());
+
+        let mut args = build_args(":tag [{file}]");
+        assert_eq!(Ok(Some(Op::new_tag(Some("[{file}]".to_string())))), parse_op(&mut args));
+        assert!(args.next().is_none());
+    }
+
     #[test]
     fn test_parse_slice() {
         // limit
@@ -517,6 +852,89 @@ mod tests {
         assert_eq!(Some("-10".to_string()), args.next());
     }
 
+    #[test]
+    fn test_parse_stats() {
+        let mut args = build_args(":stats");
+        assert_eq!(Ok(Some(Op::new_stats(StatsKind::ALL.to_vec(), None))), parse_op(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":stats sum :count");
+        assert_eq!(Ok(Some(Op::new_stats(vec![StatsKind::Sum], None))), parse_op(&mut args));
+        assert_eq!(Some(":count".to_string()), args.next());
+
+        let mut args = build_args(":stats AVG max avg 0");
+        assert_eq!(
+            Ok(Some(Op::new_stats(vec![StatsKind::Avg, StatsKind::Max], Some(Num::from(0))))),
+            parse_op(&mut args)
+        );
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":stats median all 1.5");
+        assert_eq!(
+            Ok(Some(Op::Stats {
+                kinds: vec![
+                    StatsKind::Median,
+                    StatsKind::Sum,
+                    StatsKind::Avg,
+                    StatsKind::Min,
+                    StatsKind::Max,
+                    StatsKind::Stddev
+                ],
+                default: Some(Num::from(1.5))
+            })),
+            parse_op(&mut args)
+        );
+        assert!(args.next().is_none());
+    }
+
+    #[test]
+    fn test_parse_freq() {
+        let mut args = build_args(":freq");
+        assert_eq!(Ok(Some(Op::new_freq(false, None, None))), parse_op(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":freq nocase desc :count");
+        assert_eq!(Ok(Some(Op::new_freq(true, Some(true), None))), parse_op(&mut args));
+        assert_eq!(Some(":count".to_string()), args.next());
+
+        let mut args = build_args(":freq asc {v}:{count}");
+        assert_eq!(Ok(Some(Op::new_freq(false, Some(false), Some("{v}:{count}".to_string())))), parse_op(&mut args));
+        assert!(args.next().is_none());
+    }
+
+    #[test]
+    fn test_parse_split() {
+        let mut args = build_args(":split");
+        assert_eq!(Ok(Some(Op::Split(SplitArg::new_blank()))), parse_op(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":split :count");
+        assert_eq!(Ok(Some(Op::Split(SplitArg::new_blank()))), parse_op(&mut args));
+        assert_eq!(Some(":count".to_string()), args.next());
+
+        let mut args = build_args(":split , keep-empty");
+        assert_eq!(Ok(Some(Op::Split(SplitArg::new_str(",".to_string(), false, true)))), parse_op(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":split x nocase keep-empty");
+        assert_eq!(Ok(Some(Op::Split(SplitArg::new_str("x".to_string(), true, true)))), parse_op(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(r":split reg \s*,\s* nocase");
+        assert_eq!(
+            Ok(Some(Op::Split(SplitArg::new_regex(r"\s*,\s*".to_string(), true, false).unwrap()))),
+            parse_op(&mut args)
+        );
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":split reg [");
+        assert!(matches!(parse_op(&mut args), Err(RpErr::ParseRegexErr { .. })));
+
+        let mut args = build_args(":split reg");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":split", arg: "regex" }), parse_op(&mut args));
+        assert!(args.next().is_none());
+    }
+
     #[test]
     fn test_parse_sort() {
         let mut args = build_args(":sort abc");
@@ -1,7 +1,7 @@
 use crate::err::RpErr;
-use crate::output::Output;
-use crate::parse::{args, OutputResult};
-use args::parse_general_file_info;
+use crate::output::{Output, SplitBy};
+use crate::parse::{args, parse_to_cmd_arg, OutputResult};
+use args::{parse_arg, parse_arg1, parse_general_file_info, parse_out_format, parse_tag_nocase, parse_usize};
 use std::iter::Peekable;
 
 pub(in crate::parse::args) fn parse_output(args: &mut Peekable<impl Iterator<Item = String>>) -> OutputResult {
@@ -9,19 +9,23 @@ pub(in crate::parse::args) fn parse_output(args: &mut Peekable<impl Iterator<Ite
         && to_cmd.eq_ignore_ascii_case(":to")
     {
         args.next(); // 消耗`to`
-        match args.peek() {
-            Some(output) => {
-                let lower_output = output.to_ascii_lowercase();
-                match lower_output.as_str() {
-                    "file" => parse_file(args),
-                    #[cfg(windows)]
-                    "clip" => parse_clip(args),
-                    "out" => parse_std_out(args),
-                    _ => Ok(Output::new_std_out()),
-                }
-            }
-            None => Ok(Output::new_std_out()),
+        let mut outputs = Vec::new();
+        while let Some(output) = args.peek() {
+            let lower_output = output.to_ascii_lowercase();
+            outputs.push(match lower_output.as_str() {
+                "file" => parse_file(args)?,
+                "split" => parse_split(args)?,
+                "cmd" => parse_cmd(args)?,
+                #[cfg(windows)]
+                "clip" => parse_clip(args)?,
+                "out" => parse_std_out(args)?,
+                _ => match parse_out_format(args, ":to")? {
+                    Some(format) => Output::new_format(format),
+                    None => break,
+                },
+            });
         }
+        Ok(if outputs.is_empty() { Output::new_std_out() } else { Output::new_tee(outputs) })
     } else {
         Ok(Output::new_std_out())
     }
@@ -29,13 +33,45 @@ pub(in crate::parse::args) fn parse_output(args: &mut Peekable<impl Iterator<Ite
 
 fn parse_file(args: &mut Peekable<impl Iterator<Item = String>>) -> OutputResult {
     args.next(); // 消耗`file`
-    if let Some((file, append, crlf)) = parse_general_file_info(args, false) {
-        Ok(Output::new_file(file, append, crlf))
+    if let Some((file, append, crlf, compress, level)) = parse_general_file_info(args, ":to file", false)? {
+        Ok(Output::new_file(file, append, crlf, compress, level))
     } else {
         Err(RpErr::MissingArg { cmd: ":to file", arg: "file" })
     }
 }
 
+fn parse_split(args: &mut Peekable<impl Iterator<Item = String>>) -> OutputResult {
+    args.next(); // 消耗`split`
+    let Some((template, append, crlf, compress, level)) = parse_general_file_info(args, ":to split", false)? else {
+        return Err(RpErr::MissingArg { cmd: ":to split", arg: "template" });
+    };
+    if !parse_tag_nocase(args, "by") {
+        return Err(RpErr::MissingArg { cmd: ":to split", arg: "by" });
+    }
+    let by = if parse_tag_nocase(args, "reg") {
+        SplitBy::new_reg(parse_arg(args).ok_or(RpErr::MissingArg { cmd: ":to split", arg: "regex" })?)?
+    } else if parse_tag_nocase(args, "batch") {
+        let batch = parse_usize(":to split", "batch", args)?;
+        if batch == 0 {
+            return Err(RpErr::ArgParseErr {
+                cmd: ":to split",
+                arg: "batch",
+                arg_value: batch.to_string(),
+                error: "positive integer is required".to_string(),
+            });
+        }
+        SplitBy::Batch(batch)
+    } else {
+        return Err(RpErr::MissingArg { cmd: ":to split", arg: "reg|batch" });
+    };
+    Ok(Output::new_split(template, by, append, crlf, compress, level))
+}
+
+fn parse_cmd(args: &mut Peekable<impl Iterator<Item = String>>) -> OutputResult {
+    args.next(); // 消耗`cmd`
+    parse_to_cmd_arg(parse_arg1(args, ":to cmd", "program")?, "program")
+}
+
 #[cfg(windows)]
 fn parse_clip(args: &mut Peekable<impl Iterator<Item = String>>) -> OutputResult {
     args.next(); // 消耗`clip`
@@ -59,3 +95,123 @@ fn parse_std_out(args: &mut Peekable<impl Iterator<Item = String>>) -> OutputRes
     args.next(); // 消耗`out`
     Ok(Output::new_std_out())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::CmdArg;
+    use crate::compress::Compress;
+    use crate::out_format::OutFormat;
+    use crate::parse::args::build_args;
+
+    #[test]
+    fn test_parse_tee() {
+        let mut args = build_args(":to out file a.txt file b.txt append crlf");
+        assert_eq!(
+            Ok(Output::new_tee(vec![
+                Output::new_std_out(),
+                Output::new_file("a.txt".to_string(), false, None, None, None),
+                Output::new_file("b.txt".to_string(), true, Some(true), None, None),
+            ])),
+            parse_output(&mut args)
+        );
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":to file out file file.txt gzip cmd sort -u");
+        assert_eq!(
+            Ok(Output::new_tee(vec![
+                Output::new_file("out".to_string(), false, None, None, None),
+                Output::new_file("file.txt".to_string(), false, None, Some(Compress::Gzip), None),
+                Output::new_cmd(CmdArg::new(vec!["sort".to_string(), "-u".to_string()]), None),
+            ])),
+            parse_output(&mut args)
+        );
+
+        let mut args = build_args(":to file out.gz 9 file out.xz crlf 6");
+        assert_eq!(
+            Ok(Output::new_tee(vec![
+                Output::new_file("out.gz".to_string(), false, None, None, Some(9)),
+                Output::new_file("out.xz".to_string(), false, Some(true), None, Some(6)),
+            ])),
+            parse_output(&mut args)
+        );
+        assert!(args.next().is_none());
+        assert!(parse_output(&mut build_args(":to file out.txt 9")).is_err());
+        assert!(parse_output(&mut build_args(":to file out.bz2 0")).is_err());
+
+        let mut args = build_args(":to json file a.json csv name,age");
+        assert_eq!(
+            Ok(Output::new_tee(vec![
+                Output::new_format(OutFormat::Json),
+                Output::new_file("a.json".to_string(), false, None, None, None),
+                Output::new_format(OutFormat::new_csv("name,age")),
+            ])),
+            parse_output(&mut args)
+        );
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":to", arg: "header" }), parse_output(&mut build_args(":to csv")));
+
+        let mut args = build_args(":to out other");
+        assert_eq!(Ok(Output::new_std_out()), parse_output(&mut args));
+        assert_eq!(Some("other".to_string()), args.next());
+    }
+
+    #[test]
+    fn test_parse_split() {
+        let mut args = build_args(":to split out/{key}.txt by reg ^(\\w+)");
+        assert_eq!(
+            Ok(Output::new_split(
+                "out/{key}.txt".to_string(),
+                SplitBy::new_reg(r"^(\w+)".to_string()).unwrap(),
+                false,
+                None,
+                None,
+                None
+            )),
+            parse_output(&mut args)
+        );
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":to split {key}.gz append crlf gzip 9 BY batch 100 out");
+        assert_eq!(
+            Ok(Output::new_tee(vec![
+                Output::new_split(
+                    "{key}.gz".to_string(),
+                    SplitBy::Batch(100),
+                    true,
+                    Some(true),
+                    Some(Compress::Gzip),
+                    Some(9)
+                ),
+                Output::new_std_out(),
+            ])),
+            parse_output(&mut args)
+        );
+
+        let mut args = build_args(":to split {key}.txt");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":to split", arg: "by" }), parse_output(&mut args));
+        let mut args = build_args(":to split {key}.txt by");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":to split", arg: "reg|batch" }), parse_output(&mut args));
+        let mut args = build_args(":to split {key}.txt by batch 0");
+        assert!(parse_output(&mut args).is_err());
+        let mut args = build_args(":to split {key}.txt by reg (");
+        assert!(parse_output(&mut args).is_err());
+    }
+
+    #[test]
+    fn test_parse_cmd() {
+        let mut args = build_args(":to cmd sort -u");
+        assert_eq!(
+            Ok(Output::new_cmd(CmdArg::new(vec!["sort".to_string(), "-u".to_string()]), None)),
+            parse_output(&mut args)
+        );
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":to CMD CRLF unix2dos");
+        assert_eq!(Ok(Output::new_cmd(CmdArg::new(vec!["unix2dos".to_string()]), Some(true))), parse_output(&mut args));
+
+        let mut args = build_args(":to cmd lf");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":to cmd", arg: "program" }), parse_output(&mut args));
+        let mut args = build_args(":to cmd");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":to cmd", arg: "program" }), parse_output(&mut args));
+    }
+}
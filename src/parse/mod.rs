@@ -1,9 +1,14 @@
+use crate::cmd::CmdArg;
+use crate::compress::Compress;
 use crate::condition::Condition;
 use crate::config::Config;
 use crate::err::RpErr;
+use crate::file::FileArg;
 use crate::input::Input;
+use crate::op::exec::{ExecArg, ExecMode};
 use crate::op::Op;
 use crate::output::Output;
+use glob::Pattern;
 use nom::error::{ContextError, ErrorKind, FromExternalError, ParseError};
 use nom::IResult;
 use nom_language::error::{VerboseError, VerboseErrorKind};
@@ -21,6 +26,8 @@ type OutputResult = Result<Output, RpErr>;
 type CondResult = Result<Condition, RpErr>;
 type OpsIResult<'a> = IResult<&'a str, Vec<Op>, RpParseErr<'a>>;
 type OpIResult<'a> = IResult<&'a str, Op, RpParseErr<'a>>;
+/// 文件输出信息：文件路径、是否追加、是否使用CRLF、压缩格式、压缩级别
+type FileInfo = (String, bool, Option<bool>, Option<Compress>, Option<u32>);
 
 /// 解析错误的类型
 #[derive(Debug, Clone, PartialEq)]
@@ -71,3 +78,97 @@ impl From<nom::Err<RpParseErr<'_>>> for RpErr {
         }
     }
 }
+
+/// 解析`:file`的参数，文件列表之后为选项，遇到第一个选项时文件列表结束。
+fn parse_file_arg(args: Vec<String>, arg: &'static str) -> Result<FileArg, RpErr> {
+    let mut args = args.into_iter().peekable();
+    let mut file_arg = FileArg::new(Vec::new());
+    while let Some(file) = args.next_if(|file| !is_file_option(file)) {
+        file_arg.files.push(file);
+    }
+    if file_arg.files.is_empty() {
+        Err(RpErr::MissingArg { cmd: ":file", arg })?
+    }
+    while let Some(option) = args.next() {
+        match option.to_ascii_lowercase().as_str() {
+            "recursive" => file_arg.recursive = true,
+            "follow" => file_arg.follow = true,
+            "include" => file_arg.include.push(parse_glob(args.next())?),
+            "exclude" => file_arg.exclude.push(parse_glob(args.next())?),
+            name => match Compress::from_name(name) {
+                Some(compress) => file_arg.compress = Some(compress),
+                None => Err(RpErr::UnexpectedRemaining { cmd: ":file", arg: "[options]", remaining: option })?,
+            },
+        }
+    }
+    Ok(file_arg)
+}
+
+fn is_file_option(arg: &str) -> bool {
+    ["recursive", "include", "exclude", "follow"].iter().any(|option| arg.eq_ignore_ascii_case(option))
+        || Compress::from_name(arg).is_some()
+}
+
+fn parse_glob(glob: Option<String>) -> Result<Pattern, RpErr> {
+    match glob {
+        Some(glob) => Pattern::new(&glob).map_err(|err| RpErr::ArgParseErr {
+            cmd: ":file",
+            arg: "<glob>",
+            arg_value: glob,
+            error: err.to_string(),
+        }),
+        None => Err(RpErr::MissingArg { cmd: ":file", arg: "<glob>" }),
+    }
+}
+
+/// 解析`:exec`的参数，选项之后为程序和程序参数，遇到第一个非选项时选项结束。
+fn parse_exec_arg(args: Vec<String>, arg: &'static str) -> Result<ExecArg, RpErr> {
+    let mut args = args.into_iter().peekable();
+    let (mut mode, mut batch, mut parallel) = (ExecMode::Out, 1, 1);
+    while let Some(option) = args.next_if(|option| is_exec_option(option)) {
+        match option.to_ascii_lowercase().as_str() {
+            "out" => mode = ExecMode::Out,
+            "filter" => mode = ExecMode::Filter,
+            "peek" => mode = ExecMode::Peek,
+            "batch" => batch = parse_exec_count(args.next(), "<batch>")?,
+            _ => parallel = parse_exec_count(args.next(), "<parallel>")?,
+        }
+    }
+    let values: Vec<String> = args.collect();
+    if values.is_empty() {
+        Err(RpErr::MissingArg { cmd: ":exec", arg })?
+    }
+    Ok(ExecArg { mode, batch, parallel, ..ExecArg::new(CmdArg::new(values)) })
+}
+
+fn is_exec_option(arg: &str) -> bool {
+    ["out", "filter", "peek", "batch", "parallel"].iter().any(|option| arg.eq_ignore_ascii_case(option))
+}
+
+fn parse_exec_count(count: Option<String>, arg: &'static str) -> Result<usize, RpErr> {
+    match count {
+        Some(count) => match count.parse::<usize>() {
+            Ok(count) if count > 0 => Ok(count),
+            _ => Err(RpErr::ArgParseErr {
+                cmd: ":exec",
+                arg,
+                arg_value: count,
+                error: "positive integer is required".to_string(),
+            }),
+        },
+        None => Err(RpErr::MissingArg { cmd: ":exec", arg }),
+    }
+}
+
+/// 解析`:to cmd`的参数，可选的换行符之后为程序和程序参数。
+fn parse_to_cmd_arg(args: Vec<String>, arg: &'static str) -> Result<Output, RpErr> {
+    let mut args = args.into_iter().peekable();
+    let crlf = args
+        .next_if(|crlf| crlf.eq_ignore_ascii_case("lf") || crlf.eq_ignore_ascii_case("crlf"))
+        .map(|crlf| crlf.eq_ignore_ascii_case("crlf"));
+    let values: Vec<String> = args.collect();
+    if values.is_empty() {
+        Err(RpErr::MissingArg { cmd: ":to cmd", arg })?
+    }
+    Ok(Output::new_cmd(CmdArg::new(values), crlf))
+}
@@ -1,4 +1,5 @@
-use crate::condition::{Condition, Select, TextSelectMode};
+use crate::condition::{Condition, LiteralSelectMode, Select, TextSelectMode};
+use crate::json::JsonPath;
 use crate::parse::token::{arg, arg_end, map_res_failure, parse_num};
 use crate::parse::RpParseErr;
 use nom::branch::alt;
@@ -6,10 +7,51 @@ use nom::bytes::complete::tag_no_case;
 use nom::character::complete::{char, space1, usize};
 use nom::combinator::{map, opt, value};
 use nom::error::context;
-use nom::sequence::{preceded, terminated};
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, preceded, terminated};
 use nom::{IResult, Parser};
 
+/// 解析条件表达式，优先级从高到低依次为：not、and、or，支持使用`( `和`) `调整优先级。
 pub(in crate::parse) fn parse_cond(input: &str) -> IResult<&str, Condition, RpParseErr<'_>> {
+    context("Cond::Or", map(separated_list1((tag_no_case("or"), space1), parse_cond_and), Condition::new_or))
+        .parse(input)
+}
+
+fn parse_cond_and(input: &str) -> IResult<&str, Condition, RpParseErr<'_>> {
+    context("Cond::And", map(separated_list1((tag_no_case("and"), space1), parse_cond_not), Condition::new_and))
+        .parse(input)
+}
+
+fn parse_cond_not(input: &str) -> IResult<&str, Condition, RpParseErr<'_>> {
+    alt((
+        context("Cond::Not", map(preceded((tag_no_case("not"), space1), parse_cond_not), Condition::not)),
+        context("Cond::Group", delimited((char('('), space1), parse_cond, (char(')'), space1))),
+        parse_cond_select,
+    ))
+    .parse(input)
+}
+
+fn parse_cond_select(input: &str) -> IResult<&str, Condition, RpParseErr<'_>> {
+    alt((parse_cond_json, parse_cond_simple)).parse(input)
+}
+
+/// 解析JSON条件，内部的选择条件会消耗结尾空格。
+fn parse_cond_json(input: &str) -> IResult<&str, Condition, RpParseErr<'_>> {
+    context(
+        "Cond::Json",
+        map(
+            (
+                terminated(opt((tag_no_case("not"), space1)), (tag_no_case("json"), space1)),
+                map_res_failure(terminated(context("<path>", arg), space1), |path| JsonPath::new(path, "json")),
+                parse_cond_select,
+            ),
+            |(not_opt, path, cond)| Condition::new(Select::new_json(path, cond), not_opt.is_some()),
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_cond_simple(input: &str) -> IResult<&str, Condition, RpParseErr<'_>> {
     terminated(
         alt((
             context(
@@ -70,6 +112,38 @@ pub(in crate::parse) fn parse_cond(input: &str) -> IResult<&str, Condition, RpPa
                     |(not_opt, regex)| Condition::new(regex, not_opt.is_some()),
                 ),
             ),
+            context(
+                "Cond::RegFind",
+                map_res_failure(
+                    (
+                        terminated(opt((tag_no_case("not"), space1)), (tag_no_case("find"), space1)),
+                        context("<exp>", arg),
+                        opt(preceded(space1, tag_no_case("nocase"))),
+                    ),
+                    |(not_opt, regex, nocase_opt)| {
+                        Ok(Condition::new(Select::new_reg_find(&regex, nocase_opt.is_some())?, not_opt.is_some()))
+                    },
+                ),
+            ),
+            context(
+                "Cond::Literal",
+                map(
+                    (
+                        opt((tag_no_case("not"), space1)),
+                        alt((
+                            value(LiteralSelectMode::Contains, (tag_no_case("contains"), space1)),
+                            value(LiteralSelectMode::Prefix, (tag_no_case("prefix"), space1)),
+                            value(LiteralSelectMode::Suffix, (tag_no_case("suffix"), space1)),
+                            value(LiteralSelectMode::Eq, (tag_no_case("eq"), space1)),
+                        )),
+                        context("<text>", arg),
+                        opt(preceded(space1, tag_no_case("nocase"))),
+                    ),
+                    |(not_opt, mode, text, nocase_opt)| {
+                        Condition::new(Select::new_literal(mode, text, nocase_opt.is_some()), not_opt.is_some())
+                    },
+                ),
+            ),
         )),
         context("(trailing_space1)", space1),
     )
@@ -298,4 +372,99 @@ mod tests {
             Ok(("", Condition::new(Select::new_reg_match(r"\d+").unwrap(), true)))
         );
     }
+
+    #[test]
+    fn test_parse_cond_and_or_not() {
+        let upper = || Condition::new(Select::Text { mode: TextSelectMode::Upper }, false);
+        let lower = || Condition::new(Select::Text { mode: TextSelectMode::Lower }, false);
+        let len3 = || Condition::new(Select::TextLenSpec { spec: 3 }, false);
+        assert_eq!(parse_cond("upper and len 3 "), Ok(("", Condition::And(vec![upper(), len3()]))));
+        assert_eq!(parse_cond("upper OR len 3 "), Ok(("", Condition::Or(vec![upper(), len3()]))));
+        assert_eq!(
+            parse_cond("upper or lower and len 3 "),
+            Ok(("", Condition::Or(vec![upper(), Condition::And(vec![lower(), len3()])])))
+        );
+        assert_eq!(
+            parse_cond("( upper or lower ) and len 3 "),
+            Ok(("", Condition::And(vec![Condition::Or(vec![upper(), lower()]), len3()])))
+        );
+        assert_eq!(
+            parse_cond("not ( upper or lower ) or not len 3 "),
+            Ok(("", Condition::Or(vec![Condition::Or(vec![upper(), lower()]).not(), len3().not()])))
+        );
+        assert_eq!(parse_cond("not not upper "), Ok(("", upper().not().not())));
+        assert_eq!(parse_cond("( ( upper ) ) :to out "), Ok((":to out ", upper())));
+        assert_eq!(parse_cond("upper :to out "), Ok((":to out ", upper())));
+        assert!(parse_cond("( upper ").is_err());
+        assert!(parse_cond("(upper) ").is_err());
+    }
+
+    #[test]
+    fn test_parse_cond_literal() {
+        assert_eq!(
+            parse_cond("contains abc "),
+            Ok(("", Condition::new(Select::new_literal(LiteralSelectMode::Contains, "abc".to_string(), false), false)))
+        );
+        assert_eq!(
+            parse_cond("not prefix 'a b' nocase "),
+            Ok(("", Condition::new(Select::new_literal(LiteralSelectMode::Prefix, "a b".to_string(), true), true)))
+        );
+        assert_eq!(
+            parse_cond("suffix .txt or eq '' "),
+            Ok((
+                "",
+                Condition::Or(vec![
+                    Condition::new(Select::new_literal(LiteralSelectMode::Suffix, ".txt".to_string(), false), false),
+                    Condition::new(Select::new_literal(LiteralSelectMode::Eq, "".to_string(), false), false),
+                ])
+            ))
+        );
+        assert!(parse_cond("eq ").is_err());
+    }
+
+    #[test]
+    fn test_parse_cond_reg_find() {
+        assert_eq!(
+            parse_cond(r"find '\d+' nocase "),
+            Ok(("", Condition::new(Select::new_reg_find(r"\d+", true).unwrap(), false)))
+        );
+        assert_eq!(
+            parse_cond(r"not find '\d+' "),
+            Ok(("", Condition::new(Select::new_reg_find(r"\d+", false).unwrap(), true)))
+        );
+        assert!(matches!(
+            parse_cond("find [ "),
+            Err(nom::Err::Failure(RpParseErr::Rp((_, _, crate::err::RpErr::ParseRegexErr { .. }))))
+        ));
+    }
+    #[test]
+    fn test_parse_cond_json() {
+        let path = |path: &str| JsonPath::new(path.to_string(), "json").unwrap();
+        let num = Select::new_num_range(Some(Num::from(200)), Some(Num::from(299)));
+        assert_eq!(
+            parse_cond("json .status num 200,299 and upper "),
+            Ok((
+                "",
+                Condition::new_and(vec![
+                    Condition::new(Select::new_json(path(".status"), num.yes()), false),
+                    Select::Text { mode: TextSelectMode::Upper }.yes()
+                ])
+            ))
+        );
+        assert_eq!(
+            parse_cond("not json '.a[0]' not empty "),
+            Ok((
+                "",
+                Condition::new(
+                    Select::new_json(path(".a[0]"), Select::Text { mode: TextSelectMode::Empty }.not()),
+                    true
+                )
+            ))
+        );
+        assert!(matches!(
+            parse_cond("json a num "),
+            Err(nom::Err::Failure(RpParseErr::Rp((_, _, crate::err::RpErr::ArgParseErr { .. }))))
+        ));
+        assert!(parse_cond("json .a ").is_err());
+    }
 }
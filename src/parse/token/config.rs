@@ -1,12 +1,13 @@
 use crate::config::Config;
+use crate::parse::token::{arg, map_res_failure};
 use crate::parse::RpParseErr;
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::space1;
-use nom::combinator::map;
+use nom::combinator::{map, opt, verify};
 use nom::error::context;
 use nom::multi::many0;
-use nom::sequence::terminated;
+use nom::sequence::{preceded, terminated};
 use nom::{IResult, Parser};
 
 pub(crate) fn parse_configs(input: &str) -> IResult<&str, Vec<Config>, RpParseErr<'_>> {
@@ -23,6 +24,29 @@ fn parse_config(input: &str) -> IResult<&str, Config, RpParseErr<'_>> {
                 context("Config::Verbose", map(alt((tag("-v"), tag("--verbose"))), |_| Config::Verbose)),
                 context("Config::DryRun", map(alt((tag("-d"), tag("--dry-run"))), |_| Config::DryRun)),
                 context("Config::Nocase", map(alt((tag("-n"), tag("--nocase"))), |_| Config::Nocase)),
+                context("Config::SkipErr", map(alt((tag("-s"), tag("--skip-err"))), |_| Config::SkipErr)),
+                context(
+                    "Config::RecordSep",
+                    map_res_failure(preceded((alt((tag("-r"), tag("--record-sep"))), space1), arg), |record_sep| {
+                        Ok(Config::RecordSep(record_sep.parse()?))
+                    }),
+                ),
+                context(
+                    "Config::InPlace",
+                    map(
+                        preceded(
+                            alt((tag("-i"), tag("--in-place"))),
+                            opt(preceded(space1, verify(arg, |backup: &String| !backup.starts_with(['-', ':'])))),
+                        ),
+                        Config::InPlace,
+                    ),
+                ),
+                context(
+                    "Config::Encoding",
+                    map_res_failure(preceded((alt((tag("-e"), tag("--encoding"))), space1), arg), |encoding| {
+                        Ok(Config::Encoding(encoding.parse()?))
+                    }),
+                ),
             )),
             space1,
         ),
@@ -33,6 +57,7 @@ fn parse_config(input: &str) -> IResult<&str, Config, RpParseErr<'_>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::encoding::{RecordSep, TextEncoding};
 
     #[test]
     fn test_parse_config() {
@@ -46,6 +71,16 @@ mod tests {
         assert_eq!(parse_config("--dry-run "), Ok(("", Config::DryRun)));
         assert_eq!(parse_config("-n "), Ok(("", Config::Nocase)));
         assert_eq!(parse_config("--nocase "), Ok(("", Config::Nocase)));
+        assert_eq!(parse_config("-s "), Ok(("", Config::SkipErr)));
+        assert_eq!(parse_config("--skip-err "), Ok(("", Config::SkipErr)));
+        assert_eq!(parse_config("-e gbk "), Ok(("", Config::Encoding(TextEncoding::Gbk))));
+        assert_eq!(parse_config("--encoding  latin1 "), Ok(("", Config::Encoding(TextEncoding::Latin1))));
+        assert!(parse_config("-e ascii ").is_err());
+        assert_eq!(parse_config("-r para "), Ok(("", Config::RecordSep(RecordSep::Para))));
+        assert_eq!(parse_config(r"--record-sep \0 "), Ok(("", Config::RecordSep(RecordSep::Str("\0".to_string())))));
+        assert_eq!(parse_config("-i "), Ok(("", Config::InPlace(None))));
+        assert_eq!(parse_config("-i :file "), Ok((":file ", Config::InPlace(None))));
+        assert_eq!(parse_config("--in-place .bak "), Ok(("", Config::InPlace(Some(".bak".to_string())))));
         assert!(parse_config("-h").is_err());
         assert!(parse_config("abc ").is_err());
     }
@@ -1,7 +1,8 @@
+use crate::cmd::CmdArg;
 use crate::input::Input;
 use crate::parse::token::parse_integer;
-use crate::parse::token::{arg_exclude_cmd, cmd_arg1};
-use crate::parse::RpParseErr;
+use crate::parse::token::{arg_exclude_cmd, cmd_arg1, map_res_failure};
+use crate::parse::{parse_file_arg, RpParseErr};
 use crate::Integer;
 use nom::branch::alt;
 use nom::bytes::complete::tag_no_case;
@@ -20,6 +21,7 @@ pub(in crate::parse) fn parse_input(input: &str) -> InputIResult<'_> {
         alt((
             parse_std_in,
             parse_file,
+            parse_cmd,
             #[cfg(windows)]
             parse_clip,
             parse_of,
@@ -39,8 +41,18 @@ fn parse_std_in(input: &str) -> InputIResult<'_> {
 fn parse_file(input: &str) -> InputIResult<'_> {
     context(
         "Input::File",
-        map(terminated(cmd_arg1(":file", "<file>"), context("(trailing_space1)", space1)), |files| {
-            Input::new_file(files)
+        map_res_failure(terminated(cmd_arg1(":file", "<file>"), context("(trailing_space1)", space1)), |args| {
+            Ok(Input::new_file(parse_file_arg(args, "<file>")?))
+        }),
+    )
+    .parse(input)
+}
+
+fn parse_cmd(input: &str) -> InputIResult<'_> {
+    context(
+        "Input::Cmd",
+        map(terminated(cmd_arg1(":cmd", "<program>"), context("(trailing_space1)", space1)), |values| {
+            Input::new_cmd(CmdArg::new(values))
         }),
     )
     .parse(input)
@@ -125,6 +137,9 @@ fn parse_repeat(input: &str) -> InputIResult<'_> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::compress::Compress;
+    use crate::file::FileArg;
+    use glob::Pattern;
 
     #[test]
     fn test_parse_std_in() {
@@ -135,20 +150,74 @@ mod tests {
 
     #[test]
     fn test_parse_file() {
-        assert_eq!(parse_file(":file f.txt "), Ok(("", Input::new_file(vec!["f.txt".to_string()]))));
-        assert_eq!(parse_file(":file [ "), Ok(("", Input::new_file(vec!["[".to_string()]))));
-        assert_eq!(parse_file(":file ] "), Ok(("", Input::new_file(vec!["]".to_string()]))));
+        assert_eq!(parse_file(":file f.txt "), Ok(("", Input::new_file(FileArg::new(vec!["f.txt".to_string()])))));
+        assert_eq!(parse_file(":file [ "), Ok(("", Input::new_file(FileArg::new(vec!["[".to_string()])))));
+        assert_eq!(parse_file(":file ] "), Ok(("", Input::new_file(FileArg::new(vec!["]".to_string()])))));
         assert_eq!(
             parse_file(":file [ ] [] "),
-            Ok(("", Input::new_file(vec!["[".to_string(), "]".to_string(), "[]".to_string()])))
+            Ok(("", Input::new_file(FileArg::new(vec!["[".to_string(), "]".to_string(), "[]".to_string()]))))
         );
-        assert_eq!(parse_file(r#":file "f .txt" "#), Ok(("", Input::new_file(vec!["f .txt".to_string()]))));
-        assert_eq!(parse_file(":file f.txt "), Ok(("", Input::new_file(vec!["f.txt".to_string()]))));
+        assert_eq!(
+            parse_file(r#":file "f .txt" "#),
+            Ok(("", Input::new_file(FileArg::new(vec!["f .txt".to_string()]))))
+        );
+        assert_eq!(parse_file(":file f.txt "), Ok(("", Input::new_file(FileArg::new(vec!["f.txt".to_string()])))));
         assert_eq!(
             parse_file(r#":file f.txt "f .txt" "#),
-            Ok(("", Input::new_file(vec!["f.txt".to_string(), "f .txt".to_string()])))
+            Ok(("", Input::new_file(FileArg::new(vec!["f.txt".to_string(), "f .txt".to_string()]))))
         );
         assert!(parse_file(":file ").is_err());
+        assert_eq!(
+            parse_file(":file a.log b.log GZIP "),
+            Ok((
+                "",
+                Input::new_file(FileArg {
+                    compress: Some(Compress::Gzip),
+                    ..FileArg::new(vec!["a.log".to_string(), "b.log".to_string()])
+                })
+            ))
+        );
+        assert_eq!(
+            parse_file(":file ./xz plain "),
+            Ok((
+                "",
+                Input::new_file(FileArg { compress: Some(Compress::Plain), ..FileArg::new(vec!["./xz".to_string()]) })
+            ))
+        );
+        assert!(parse_file(":file zstd ").is_err());
+        assert_eq!(
+            parse_file(r#":file 'logs/**/*.log' Recursive exclude "*.gz" gzip "#),
+            Ok((
+                "",
+                Input::new_file(FileArg {
+                    recursive: true,
+                    exclude: vec![Pattern::new("*.gz").unwrap()],
+                    compress: Some(Compress::Gzip),
+                    ..FileArg::new(vec!["logs/**/*.log".to_string()])
+                })
+            ))
+        );
+        assert!(parse_file(":file logs include ").is_err());
+        assert_eq!(
+            parse_file(":file app.log FOLLOW "),
+            Ok(("", Input::new_file(FileArg { follow: true, ..FileArg::new(vec!["app.log".to_string()]) })))
+        );
+    }
+
+    #[test]
+    fn test_parse_cmd() {
+        assert_eq!(parse_cmd(":cmd ls "), Ok(("", Input::new_cmd(CmdArg::new(vec!["ls".to_string()])))));
+        assert_eq!(
+            parse_cmd(r#":CMD git log "--format=%h %s" :upper "#),
+            Ok((
+                ":upper ",
+                Input::new_cmd(CmdArg {
+                    program: "git".to_string(),
+                    args: vec!["log".to_string(), "--format=%h %s".to_string()]
+                })
+            ))
+        );
+        assert!(parse_cmd(":cmd ").is_err());
     }
 
     #[test]
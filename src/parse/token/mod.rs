@@ -4,30 +4,32 @@ pub(in crate::parse) mod input;
 pub(in crate::parse) mod op;
 pub(in crate::parse) mod output;
 
+use crate::compress::Compress;
 use crate::config::Config;
 use crate::err::RpErr;
 use crate::input::Input;
 use crate::op::Op;
+use crate::out_format::OutFormat;
 use crate::output::Output;
 use crate::parse::token::config::parse_configs;
 use crate::parse::token::input::parse_input;
 use crate::parse::token::op::parse_ops;
 use crate::parse::token::output::parse_out;
-use crate::Num;
+use crate::{Integer, Num};
 use nom::branch::alt;
 use nom::bytes::complete::{escaped, take_while1};
 use nom::bytes::complete::{tag_no_case, take_while};
-use nom::character::complete::{anychar, char, usize};
+use nom::character::complete::{anychar, char, u32, usize};
 use nom::character::complete::{none_of, space1};
-use nom::combinator::{eof, map, map_res, opt, peek, recognize, value, verify};
+use nom::combinator::{eof, map, map_opt, map_res, opt, peek, recognize, value, verify};
 use nom::error::context;
 use nom::multi::{fold_many1, many_till};
-use nom::sequence::{delimited, preceded, separated_pair};
+use nom::sequence::{delimited, preceded, separated_pair, terminated};
 use nom::{ExtendInto, IResult, Parser};
 use std::borrow::Cow;
 use std::str::FromStr;
 
-use crate::parse::RpParseErr;
+use crate::parse::{FileInfo, RpParseErr};
 /// 重新导出解析整数的函数
 pub(in crate::parse) use nom::character::complete::i64 as parse_integer;
 pub(in crate::parse) use nom::number::complete::double as parse_float;
@@ -75,14 +77,66 @@ pub(in crate::parse) fn parse_num(input: &str) -> IResult<&str, Num, RpParseErr<
 }
 
 fn general_file_info<'a>(
-    optional: bool,
-) -> impl Parser<&'a str, Output = (String, Option<&'a str>, Option<&'a str>), Error = RpParseErr<'a>> {
-    (
-        context("<file>", if optional { arg_exclude_cmd } else { arg }), // 文件
-        opt(preceded(space1, tag_no_case("append"))),                    // 是否追加
-        opt(preceded(space1, alt((tag_no_case("lf"), tag_no_case("crlf"))))), // 换行符
+    cmd: &'static str, optional: bool,
+) -> impl Parser<&'a str, Output = FileInfo, Error = RpParseErr<'a>> {
+    map_res_failure(
+        (
+            context("<file>", if optional { arg_exclude_cmd } else { arg }), // 文件
+            opt(preceded(space1, tag_no_case("append"))),                    // 是否追加
+            opt(preceded(space1, alt((tag_no_case("lf"), tag_no_case("crlf"))))), // 换行符
+            opt(preceded(space1, file_compress)),                            // 压缩格式
+            opt(preceded(space1, terminated(u32, arg_end))),                 // 压缩级别
+        ),
+        move |(file, append_opt, postfix_opt, compress, level)| {
+            // 未指定压缩格式时，根据文件扩展名推断的压缩格式校验压缩级别
+            let level_compress = compress.unwrap_or_else(|| Compress::infer(&file));
+            match level {
+                Some(level) if !level_compress.is_valid_level(level) => Err(RpErr::ArgParseErr {
+                    cmd,
+                    arg: "<level>",
+                    arg_value: level.to_string(),
+                    error: format!("invalid level for {level_compress:?}"),
+                }),
+                _ => Ok((
+                    file,
+                    append_opt.is_some(),
+                    postfix_opt.map(|s: &str| s.eq_ignore_ascii_case("crlf")),
+                    compress,
+                    level,
+                )),
+            }
+        },
+    )
+}
+
+/// 解析结构化输出格式`json|jsonl|csv <header>`
+fn out_format(input: &str) -> IResult<&str, OutFormat, RpParseErr<'_>> {
+    alt((
+        value(OutFormat::Json, (tag_no_case("json"), arg_end)),
+        value(OutFormat::Jsonl, (tag_no_case("jsonl"), arg_end)),
+        map(preceded((tag_no_case("csv"), space1), context("<header>", arg)), |header| OutFormat::new_csv(&header)),
+    ))
+    .parse(input)
+}
+
+/// 解析压缩格式`gzip|zstd|bzip2|xz|plain`
+fn file_compress(input: &str) -> IResult<&str, Compress, RpParseErr<'_>> {
+    map_opt(
+        terminated(
+            alt((
+                tag_no_case("gzip"),
+                tag_no_case("zstd"),
+                tag_no_case("bzip2"),
+                tag_no_case("xz"),
+                tag_no_case("plain"),
+            )),
+            arg_end,
+        ),
+        Compress::from_name,
     )
+    .parse(input)
 }
+
 /// 构造一个解析器，解析`cmd arg [arg ][arg ][...]`，即解析至少一个参数直到遇到下一个冒号命令，
 /// 如果参数以冒号开头需要使用`\:`代替开头的`:`。
 fn cmd_arg1<'a>(
@@ -272,6 +326,20 @@ pub(in crate::parse) fn parse_usize_range(
     .parse(input)
 }
 
+/// 解析索引范围，支持单个索引`<index>`和索引范围`<start>,<end>`，索引可以为负数。
+pub(in crate::parse) fn parse_index_range(
+    input: &str,
+) -> IResult<&str, (Option<Integer>, Option<Integer>), RpParseErr<'_>> {
+    alt((
+        verify(
+            separated_pair(context("<start>", opt(parse_integer)), char(','), context("<end>", opt(parse_integer))),
+            |(s, e)| s.is_some() || e.is_some(),
+        ),
+        map(context("<index>", parse_integer), |i| (Some(i), Some(i))),
+    ))
+    .parse(input)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
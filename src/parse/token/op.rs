@@ -1,20 +1,25 @@
 use crate::err::RpErr;
+use crate::json::JsonPath;
+use crate::op::field::{FieldArg, FieldSep};
+use crate::op::split::SplitArg;
+use crate::op::stats::StatsKind;
 use crate::op::trim::{TrimArg, TrimPos};
 use crate::op::{CaseArg, JoinInfo, Op, PeekArg, SortBy, TakeDropMode};
 use crate::parse::token::condition::parse_cond;
 use crate::parse::token::{
-    arg, arg_end, arg_exclude_cmd, general_file_info, map_res_failure, parse_arg_as, parse_usize_range,
+    arg, arg_end, arg_exclude_cmd, cmd_arg1, general_file_info, map_res_failure, out_format, parse_arg_as, parse_index_range,
+    parse_integer, parse_num, parse_usize_range,
 };
-use crate::parse::{OpIResult, OpsIResult};
+use crate::parse::{parse_exec_arg, OpIResult, OpsIResult, RpParseErr};
 use crate::{Float, Integer};
 use nom::branch::alt;
 use nom::bytes::complete::tag_no_case;
-use nom::character::complete::{space1, usize};
-use nom::combinator::{map, opt, value, verify};
+use nom::character::complete::{char, space1, usize};
+use nom::combinator::{map, map_opt, opt, value, verify};
 use nom::error::context;
 use nom::multi::{many0, many1};
 use nom::sequence::{delimited, preceded, terminated};
-use nom::Parser;
+use nom::{IResult, Parser};
 
 // TODO 2026-01-22 02:10 改造token解析结果，支持传递RpErr，补充相关UT
 pub(in crate::parse) fn parse_ops(input: &str) -> OpsIResult<'_> {
@@ -24,12 +29,22 @@ pub(in crate::parse) fn parse_ops(input: &str) -> OpsIResult<'_> {
             parse_peek,
             parse_case,
             parse_replace,
+            parse_regex_replace,
             parse_trim,
+            parse_field,
+            parse_json,
+            parse_number,
+            parse_fmt,
+            parse_tag,
+            parse_exec,
             parse_slice,
             parse_uniq,
             parse_join,
             parse_take_drop,
             parse_count,
+            parse_stats,
+            parse_freq,
+            parse_split,
             parse_sort,
         ))),
     )
@@ -42,19 +57,20 @@ fn parse_peek(input: &str) -> OpIResult<'_> {
         map(
             terminated(
                 preceded(
-                    tag_no_case(":peek"),                           // 丢弃命令
-                    opt(preceded(space1, general_file_info(true))), // 可选文件信息
+                    tag_no_case(":peek"), // 丢弃命令
+                    opt(preceded(
+                        space1,
+                        alt((
+                            map(out_format, PeekArg::Format), // 结构化输出格式
+                            map(general_file_info(":peek", true), |(file, append, crlf, compress, level)| {
+                                PeekArg::File { file, append, crlf, compress, level }
+                            }), // 文件信息
+                        )),
+                    )), // 可选
                 ),
                 context("(trailing_space1)", space1), // 结尾空格
             ),
-            |file_info| match file_info {
-                Some((file, append_opt, postfix_opt)) => Op::Peek(PeekArg::File {
-                    file,
-                    append: append_opt.is_some(),
-                    crlf: postfix_opt.map(|s| s.eq_ignore_ascii_case("crlf")),
-                }),
-                None => Op::Peek(PeekArg::StdOut),
-            },
+            |peek_arg| Op::Peek(peek_arg.unwrap_or(PeekArg::StdOut)),
         ),
     )
     .parse(input)
@@ -96,6 +112,30 @@ fn parse_replace(input: &str) -> OpIResult<'_> {
     .parse(input)
 }
 
+fn parse_regex_replace(input: &str) -> OpIResult<'_> {
+    context(
+        "Op::RegexReplace",
+        map_res_failure(
+            preceded(
+                (tag_no_case(":replacer"), arg_end), // 丢弃：命令
+                terminated(
+                    (
+                        preceded(space1, context("<regex>", arg)), // 被替换正则
+                        (
+                            preceded(space1, context("<to>", arg)),           // 替换为文本
+                            opt(preceded(space1, context("<count>", usize))), // 替换次数
+                            opt(preceded(space1, tag_no_case("nocase"))),     // 忽略大小写
+                        ),
+                    ),
+                    context("(trailing_space1)", space1), // 丢弃：结尾空格
+                ),
+            ),
+            |(regex, (to, count_opt, nocase_opt))| Op::new_regex_replace(regex, to, count_opt, nocase_opt.is_some()),
+        ),
+    )
+    .parse(input)
+}
+
 fn parse_trim(input: &str) -> OpIResult<'_> {
     context(
         "Op::Trim",
@@ -143,6 +183,124 @@ fn parse_trim(input: &str) -> OpIResult<'_> {
     .parse(input)
 }
 
+fn parse_field(input: &str) -> OpIResult<'_> {
+    context(
+        "Op::Field",
+        map(
+            terminated(
+                (
+                    preceded(
+                        (tag_no_case(":field"), arg_end),                               // 丢弃：命令
+                        context("<index>", many1(preceded(space1, parse_index_range))), // 字段索引
+                    ),
+                    opt(preceded(
+                        space1,
+                        alt((
+                            map(preceded((tag_no_case("sep"), space1), context("<delimiter>", arg)), FieldSep::Str),
+                            value(FieldSep::Csv, (tag_no_case("csv"), arg_end)),
+                            value(FieldSep::Tsv, (tag_no_case("tsv"), arg_end)),
+                            value(FieldSep::Ws, (tag_no_case("ws"), arg_end)),
+                        )),
+                    )), // 拆分方式
+                    opt(preceded((space1, tag_no_case("join"), space1), context("<out>", arg))), // 合并分隔字符串
+                ),
+                context("(trailing_space1)", space1), // 丢弃：结尾空格
+            ),
+            |(ranges, sep, join)| Op::Field(FieldArg::new(ranges, sep.unwrap_or(FieldSep::Ws), join)),
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_json(input: &str) -> OpIResult<'_> {
+    context(
+        "Op::Json",
+        map_res_failure(
+            terminated(
+                preceded((tag_no_case(":json"), space1), context("<path>", arg)), // 丢弃：命令
+                context("(trailing_space1)", space1),                             // 丢弃：结尾空格
+            ),
+            |path| Ok(Op::new_json(JsonPath::new(path, ":json")?)),
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_number(input: &str) -> OpIResult<'_> {
+    context(
+        "Op::Number",
+        map(
+            terminated(
+                preceded(
+                    (tag_no_case(":number"), arg_end), // 丢弃：命令
+                    (
+                        opt(preceded(space1, terminated(parse_start_step_in_number, arg_end))), // 起始编号和步长
+                        opt(preceded(space1, context("<fmt>", arg_exclude_cmd))),               // 格式化字符串
+                    ),
+                ),
+                context("(trailing_space1)", space1), // 丢弃：结尾空格
+            ),
+            |(start_step, fmt)| {
+                let (start, step) = start_step.unwrap_or((1, 1));
+                Op::new_number(start, step, fmt)
+            },
+        ),
+    )
+    .parse(input)
+}
+
+pub(in crate::parse) fn parse_start_step_in_number(input: &str) -> IResult<&str, (Integer, Integer), RpParseErr<'_>> {
+    map(
+        (
+            context("<start>", parse_integer),                          // 必选起始编号
+            opt(preceded(char(','), context("<step>", parse_integer))), // 可选步长
+        ),
+        |(start, step)| (start, step.unwrap_or(1)),
+    )
+    .parse(input)
+}
+
+fn parse_fmt(input: &str) -> OpIResult<'_> {
+    context(
+        "Op::Fmt",
+        map(
+            terminated(
+                preceded((tag_no_case(":fmt"), space1), context("<template>", arg)), // 格式化字符串
+                context("(trailing_space1)", space1),                                // 丢弃：结尾空格
+            ),
+            |fmt| Op::Fmt { fmt },
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_tag(input: &str) -> OpIResult<'_> {
+    context(
+        "Op::Tag",
+        map(
+            terminated(
+                preceded(
+                    (tag_no_case(":tag"), arg_end),                                // 丢弃：命令
+                    opt(preceded(space1, context("<template>", arg_exclude_cmd))), // 格式化字符串
+                ),
+                context("(trailing_space1)", space1), // 丢弃：结尾空格
+            ),
+            Op::new_tag,
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_exec(input: &str) -> OpIResult<'_> {
+    context(
+        "Op::Exec",
+        map_res_failure(terminated(cmd_arg1(":exec", "<program>"), context("(trailing_space1)", space1)), |args| {
+            Ok(Op::new_exec(parse_exec_arg(args, "<program>")?))
+        }),
+    )
+    .parse(input)
+}
+
 fn parse_slice(input: &str) -> OpIResult<'_> {
     context(
         "Op::Slice",
@@ -278,6 +436,95 @@ fn parse_count(input: &str) -> OpIResult<'_> {
     context("Op::Count", map(preceded(tag_no_case(":count"), space1), |_| Op::Count)).parse(input)
 }
 
+fn parse_stats(input: &str) -> OpIResult<'_> {
+    context(
+        "Op::Stats",
+        map(
+            terminated(
+                preceded(
+                    (tag_no_case(":stats"), arg_end), // 丢弃：命令
+                    (
+                        many0(preceded(
+                            space1,
+                            map_opt(arg_exclude_cmd, |name| StatsKind::from_name(&name)), // 统计项
+                        )),
+                        opt(preceded(space1, context("<default>", terminated(parse_num, arg_end)))), // 默认数值
+                    ),
+                ),
+                context("(trailing_space1)", space1), // 丢弃：结尾空格
+            ),
+            |(kinds, default)| Op::new_stats(kinds.into_iter().flatten().copied().collect(), default),
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_freq(input: &str) -> OpIResult<'_> {
+    context(
+        "Op::Freq",
+        map(
+            terminated(
+                preceded(
+                    (tag_no_case(":freq"), arg_end), // 丢弃：命令
+                    (
+                        opt(preceded(space1, (tag_no_case("nocase"), arg_end))), // 忽略大小写
+                        opt(preceded(
+                            space1,
+                            alt((
+                                value(true, (tag_no_case("desc"), arg_end)),
+                                value(false, (tag_no_case("asc"), arg_end)),
+                            )),
+                        )), // 排序方式
+                        opt(preceded(space1, context("<fmt>", arg_exclude_cmd))), // 格式化字符串
+                    ),
+                ),
+                context("(trailing_space1)", space1), // 丢弃：结尾空格
+            ),
+            |(nocase, desc, fmt)| Op::new_freq(nocase.is_some(), desc, fmt),
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_split(input: &str) -> OpIResult<'_> {
+    context(
+        "Op::Split",
+        map_res_failure(
+            terminated(
+                preceded(
+                    (tag_no_case(":split"), arg_end), // 丢弃：命令
+                    opt(preceded(
+                        space1,
+                        (
+                            alt((
+                                map(preceded((tag_no_case("reg"), space1), context("<regex>", arg)), |regex| {
+                                    (regex, true)
+                                }), // 分隔正则
+                                map(context("<delimiter>", arg_exclude_cmd), |delimiter| (delimiter, false)), // 分隔字符串
+                            )),
+                            opt(preceded(space1, tag_no_case("nocase"))), // 忽略大小写
+                            opt(preceded(space1, tag_no_case("keep-empty"))), // 保留空字符串
+                        ),
+                    )),
+                ),
+                context("(trailing_space1)", space1), // 丢弃：结尾空格
+            ),
+            |split_opt| {
+                Ok(Op::Split(match split_opt {
+                    Some(((pattern, true), nocase, keep_empty)) => {
+                        SplitArg::new_regex(pattern, nocase.is_some(), keep_empty.is_some())?
+                    }
+                    Some(((pattern, false), nocase, keep_empty)) => {
+                        SplitArg::new_str(pattern, nocase.is_some(), keep_empty.is_some())
+                    }
+                    None => SplitArg::new_blank(),
+                }))
+            },
+        ),
+    )
+    .parse(input)
+}
+
 fn parse_sort(input: &str) -> OpIResult<'_> {
     context(
         "Op::Sort",
@@ -338,7 +585,12 @@ fn parse_sort(input: &str) -> OpIResult<'_> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Num;
+    use crate::cmd::CmdArg;
+    use crate::compress::Compress;
     use crate::condition::{Condition, Select};
+    use crate::op::exec::{ExecArg, ExecMode};
+    use crate::out_format::OutFormat;
 
     #[test]
     fn test_parse_case() {
@@ -383,6 +635,83 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_regex_replace() {
+        assert_eq!(
+            parse_regex_replace(r#":replacer '(\d+)' "<$1>" "#),
+            Ok(("", Op::new_regex_replace(r"(\d+)".to_string(), "<$1>".to_string(), None, false).unwrap()))
+        );
+        assert_eq!(
+            parse_regex_replace(":replacer abc 123 5 "),
+            Ok(("", Op::new_regex_replace("abc".to_string(), "123".to_string(), Some(5), false).unwrap()))
+        );
+        assert_eq!(
+            parse_regex_replace(r#":replacer abc "" 5 nocase "#),
+            Ok(("", Op::new_regex_replace("abc".to_string(), "".to_string(), Some(5), true).unwrap()))
+        );
+        assert_eq!(
+            parse_regex_replace(r#":replacer abc def nocase "#),
+            Ok(("", Op::new_regex_replace("abc".to_string(), "def".to_string(), None, true).unwrap()))
+        );
+        assert!(parse_regex_replace(":replace abc def ").is_err());
+        assert!(matches!(
+            parse_regex_replace(":replacer [ def "),
+            Err(nom::Err::Failure(crate::parse::RpParseErr::Rp((_, _, RpErr::ParseRegexErr { .. }))))
+        ));
+    }
+
+    #[test]
+    fn test_parse_stats() {
+        assert_eq!(parse_stats(":stats "), Ok(("", Op::new_stats(StatsKind::ALL.to_vec(), None))));
+        assert_eq!(parse_stats(":stats sum :count "), Ok((":count ", Op::new_stats(vec![StatsKind::Sum], None))));
+        assert_eq!(
+            parse_stats(":stats AVG max avg 0 "),
+            Ok(("", Op::new_stats(vec![StatsKind::Avg, StatsKind::Max], Some(Num::from(0)))))
+        );
+        assert_eq!(
+            parse_stats(":stats stddev -1.5 "),
+            Ok(("", Op::new_stats(vec![StatsKind::Stddev], Some(Num::from(-1.5)))))
+        );
+        assert_eq!(parse_stats(":stats abc "), Ok(("abc ", Op::new_stats(StatsKind::ALL.to_vec(), None))));
+        assert!(parse_stats(":statsx ").is_err());
+    }
+
+    #[test]
+    fn test_parse_freq() {
+        assert_eq!(parse_freq(":freq "), Ok(("", Op::new_freq(false, None, None))));
+        assert_eq!(parse_freq(":freq nocase DESC :count "), Ok((":count ", Op::new_freq(true, Some(true), None))));
+        assert_eq!(
+            parse_freq(":freq asc '{v}: {count}' "),
+            Ok(("", Op::new_freq(false, Some(false), Some("{v}: {count}".to_owned()))))
+        );
+        assert_eq!(parse_freq(":freq nocasex "), Ok(("", Op::new_freq(false, None, Some("nocasex".to_owned())))));
+        assert!(parse_freq(":freqs ").is_err());
+    }
+
+    #[test]
+    fn test_parse_split() {
+        assert_eq!(parse_split(":split "), Ok(("", Op::Split(SplitArg::new_blank()))));
+        assert_eq!(parse_split(":split :count "), Ok((":count ", Op::Split(SplitArg::new_blank()))));
+        assert_eq!(parse_split(":split , "), Ok(("", Op::Split(SplitArg::new_str(",".to_owned(), false, false)))));
+        assert_eq!(
+            parse_split(":split x nocase keep-empty "),
+            Ok(("", Op::Split(SplitArg::new_str("x".to_owned(), true, true))))
+        );
+        assert_eq!(
+            parse_split(":split regex keep-empty "),
+            Ok(("", Op::Split(SplitArg::new_str("regex".to_owned(), false, true))))
+        );
+        assert_eq!(
+            parse_split(r#":split reg '\s*,\s*' nocase "#),
+            Ok(("", Op::Split(SplitArg::new_regex(r"\s*,\s*".to_owned(), true, false).unwrap())))
+        );
+        assert!(parse_split(":splitx ").is_err());
+        assert!(matches!(
+            parse_split(":split reg [ "),
+            Err(nom::Err::Failure(crate::parse::RpParseErr::Rp((_, _, RpErr::ParseRegexErr { .. }))))
+        ));
+    }
+
     #[test]
     fn test_parse_trim() {
         // trim
@@ -495,6 +824,128 @@ mod tests {
         assert!(parse_trim(":rtrimr ").is_err());
     }
 
+    #[test]
+    fn test_parse_field() {
+        assert_eq!(
+            parse_field(":field 0 -1 2,3 -2, ,-3 "),
+            Ok((
+                "",
+                Op::Field(FieldArg::new(
+                    vec![
+                        (Some(0), Some(0)),
+                        (Some(-1), Some(-1)),
+                        (Some(2), Some(3)),
+                        (Some(-2), None),
+                        (None, Some(-3))
+                    ],
+                    FieldSep::Ws,
+                    None
+                ))
+            ))
+        );
+        assert_eq!(
+            parse_field(r#":field 1 sep "::" join ';' "#),
+            Ok((
+                "",
+                Op::Field(FieldArg::new(
+                    vec![(Some(1), Some(1))],
+                    FieldSep::Str("::".to_owned()),
+                    Some(";".to_owned())
+                ))
+            ))
+        );
+        assert_eq!(
+            parse_field(":field 1 CSV :count "),
+            Ok((":count ", Op::Field(FieldArg::new(vec![(Some(1), Some(1))], FieldSep::Csv, None))))
+        );
+        assert_eq!(
+            parse_field(":field 1 tsv join , "),
+            Ok(("", Op::Field(FieldArg::new(vec![(Some(1), Some(1))], FieldSep::Tsv, Some(",".to_owned())))))
+        );
+        assert_eq!(
+            parse_field(":field 1 ws "),
+            Ok(("", Op::Field(FieldArg::new(vec![(Some(1), Some(1))], FieldSep::Ws, None))))
+        );
+        assert!(parse_field(":field csv ").is_err());
+        assert!(parse_field(":field , ").is_err());
+        assert!(parse_field(":fields 1 ").is_err());
+    }
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(parse_number(":number "), Ok(("", Op::new_number(1, 1, None))));
+        assert_eq!(parse_number(":number :count "), Ok((":count ", Op::new_number(1, 1, None))));
+        assert_eq!(parse_number(":number 0 "), Ok(("", Op::new_number(0, 1, None))));
+        assert_eq!(parse_number(":number 10,-2 "), Ok(("", Op::new_number(10, -2, None))));
+        assert_eq!(
+            parse_number(":number '{i:>3}. {v}' "),
+            Ok(("", Op::new_number(1, 1, Some("{i:>3}. {v}".to_owned()))))
+        );
+        assert_eq!(parse_number(":number -1,2 #{i} "), Ok(("", Op::new_number(-1, 2, Some("#{i}".to_owned())))));
+        assert_eq!(parse_number(":number 1x "), Ok(("", Op::new_number(1, 1, Some("1x".to_owned())))));
+        assert!(parse_number(":numbers ").is_err());
+    }
+
+    #[test]
+    fn test_parse_fmt() {
+        assert_eq!(parse_fmt(":fmt '[{v}]' "), Ok(("", Op::Fmt { fmt: "[{v}]".to_owned() })));
+        assert_eq!(parse_fmt(":fmt {n:08.3} :count "), Ok((":count ", Op::Fmt { fmt: "{n:08.3}".to_owned() })));
+        assert!(parse_fmt(":fmt ").is_err());
+        assert!(parse_fmt(":fmtx {v} ").is_err());
+    }
+
+    #[test]
+    fn test_parse_exec() {
+        assert_eq!(
+            parse_exec(r#":exec sh -c "echo {v}" :upper "#),
+            Ok((
+                ":upper ",
+                Op::new_exec(ExecArg::new(CmdArg::new(vec![
+                    "sh".to_string(),
+                    "-c".to_string(),
+                    "echo {v}".to_string()
+                ])))
+            ))
+        );
+        assert_eq!(
+            parse_exec(":exec parallel 2 Peek batch 3 touch "),
+            Ok((
+                "",
+                Op::new_exec(ExecArg {
+                    mode: ExecMode::Peek,
+                    batch: 3,
+                    parallel: 2,
+                    ..ExecArg::new(CmdArg::new(vec!["touch".to_string()]))
+                })
+            ))
+        );
+        assert!(parse_exec(":exec ").is_err());
+        assert!(parse_exec(":exec filter ").is_err());
+        assert!(parse_exec(":exec batch x echo ").is_err());
+    }
+
+    #[test]
+    fn test_parse_json() {
+        assert_eq!(
+            parse_json(":json '.a[0]' "),
+            Ok(("", Op::new_json(JsonPath::new(".a[0]".to_string(), ":json").unwrap())))
+        );
+        assert_eq!(
+            parse_json(":json . :upper "),
+            Ok((":upper ", Op::new_json(JsonPath::new(".".to_string(), ":json").unwrap())))
+        );
+        assert!(parse_json(":json ").is_err());
+        assert!(parse_json(":json a ").is_err());
+    }
+
+    #[test]
+    fn test_parse_tag() {
+        assert_eq!(parse_tag(":tag "), Ok(("", Op::Tag { fmt: "{file}:{line}: ".to_owned() })));
+        assert_eq!(parse_tag(":tag :upper "), Ok((":upper ", Op::new_tag(None))));
+        assert_eq!(parse_tag(":tag '{line} ' "), Ok(("", Op::Tag { fmt: "{line} ".to_owned() })));
+        assert!(parse_tag(":tags ").is_err());
+    }
+
     #[test]
     fn test_parse_slice() {
         // limit
@@ -531,26 +982,114 @@ mod tests {
     fn test_parse_peek() {
         assert_eq!(parse_peek(":peek "), Ok(("", Op::Peek(PeekArg::StdOut))));
         assert_eq!(parse_peek(":peek :abc "), Ok((":abc ", Op::Peek(PeekArg::StdOut))));
+        assert_eq!(parse_peek(":peek json "), Ok(("", Op::Peek(PeekArg::Format(OutFormat::Json)))));
+        assert_eq!(parse_peek(":peek csv 'a,b' "), Ok(("", Op::Peek(PeekArg::Format(OutFormat::new_csv("a,b"))))));
+        assert_eq!(
+            parse_peek(":peek ./json "),
+            Ok((
+                "",
+                Op::Peek(PeekArg::File {
+                    file: "./json".to_string(),
+                    append: false,
+                    crlf: None,
+                    compress: None,
+                    level: None
+                })
+            ))
+        );
         assert_eq!(
             parse_peek(":peek out.txt "),
-            Ok(("", Op::Peek(PeekArg::File { file: "out.txt".to_string(), append: false, crlf: None })))
+            Ok((
+                "",
+                Op::Peek(PeekArg::File {
+                    file: "out.txt".to_string(),
+                    append: false,
+                    crlf: None,
+                    compress: None,
+                    level: None
+                })
+            ))
         );
         assert_eq!(
             parse_peek(":peek out.txt append "),
-            Ok(("", Op::Peek(PeekArg::File { file: "out.txt".to_string(), append: true, crlf: None })))
+            Ok((
+                "",
+                Op::Peek(PeekArg::File {
+                    file: "out.txt".to_string(),
+                    append: true,
+                    crlf: None,
+                    compress: None,
+                    level: None
+                })
+            ))
         );
         assert_eq!(
             parse_peek(":peek out.txt append crlf "),
-            Ok(("", Op::Peek(PeekArg::File { file: "out.txt".to_string(), append: true, crlf: Some(true) })))
+            Ok((
+                "",
+                Op::Peek(PeekArg::File {
+                    file: "out.txt".to_string(),
+                    append: true,
+                    crlf: Some(true),
+                    compress: None,
+                    level: None
+                })
+            ))
         );
         assert_eq!(
             parse_peek(":peek out.txt crlf "),
-            Ok(("", Op::Peek(PeekArg::File { file: "out.txt".to_string(), append: false, crlf: Some(true) })))
+            Ok((
+                "",
+                Op::Peek(PeekArg::File {
+                    file: "out.txt".to_string(),
+                    append: false,
+                    crlf: Some(true),
+                    compress: None,
+                    level: None
+                })
+            ))
         );
         assert_eq!(
             parse_peek(r#":peek "out .txt" "#),
-            Ok(("", Op::Peek(PeekArg::File { file: "out .txt".to_string(), append: false, crlf: None })))
+            Ok((
+                "",
+                Op::Peek(PeekArg::File {
+                    file: "out .txt".to_string(),
+                    append: false,
+                    crlf: None,
+                    compress: None,
+                    level: None
+                })
+            ))
+        );
+        assert_eq!(
+            parse_peek(":peek out.txt crlf gzip 9 "),
+            Ok((
+                "",
+                Op::Peek(PeekArg::File {
+                    file: "out.txt".to_string(),
+                    append: false,
+                    crlf: Some(true),
+                    compress: Some(Compress::Gzip),
+                    level: Some(9)
+                })
+            ))
+        );
+        assert!(parse_peek(":peek out.txt gzip 10 ").is_err());
+        assert_eq!(
+            parse_peek(":peek out.zst append 19 "),
+            Ok((
+                "",
+                Op::Peek(PeekArg::File {
+                    file: "out.zst".to_string(),
+                    append: true,
+                    crlf: None,
+                    compress: None,
+                    level: Some(19)
+                })
+            ))
         );
+        assert!(parse_peek(":peek out.txt 9 ").is_err());
         assert_eq!(parse_peek(":peek :replace crlf "), Ok((":replace crlf ", Op::Peek(PeekArg::StdOut))));
     }
 
@@ -1,11 +1,12 @@
-use crate::output::Output;
-use crate::parse::token::general_file_info;
-use crate::parse::RpParseErr;
+use crate::output::{Output, SplitBy};
+use crate::parse::token::{arg, cmd_arg1, general_file_info, map_res_failure, out_format};
+use crate::parse::{parse_to_cmd_arg, RpParseErr};
 use nom::branch::alt;
 use nom::bytes::complete::tag_no_case;
-use nom::character::complete::space1;
-use nom::combinator::{map, success};
+use nom::character::complete::{space1, usize};
+use nom::combinator::{map, success, verify};
 use nom::error::context;
+use nom::multi::many1;
 use nom::sequence::{preceded, terminated};
 use nom::IResult;
 use nom::Parser;
@@ -16,58 +17,112 @@ pub(in crate::parse) fn parse_out(input: &str) -> OutputIResult<'_> {
     context(
         "Output",
         alt((
-            parse_to_std_out,
-            parse_to_file,
-            #[cfg(windows)]
-            parse_to_clip,
+            map(
+                preceded(
+                    (tag_no_case(":to"), space1), // 丢弃：命令
+                    many1(alt((
+                        parse_std_out,
+                        parse_file,
+                        parse_split,
+                        parse_cmd,
+                        parse_format,
+                        #[cfg(windows)]
+                        parse_clip,
+                    ))), // 一个或多个输出目标
+                ),
+                Output::new_tee,
+            ),
             context("Output::Out", map(success(()), |_| Output::new_std_out())), // 最后默认使用`Output::Out`
         )),
     )
     .parse(input)
 }
 
-fn parse_to_std_out(input: &str) -> OutputIResult<'_> {
+fn parse_std_out(input: &str) -> OutputIResult<'_> {
+    context("Output::StdOut", map((tag_no_case("out"), space1), |_| Output::new_std_out())).parse(input)
+}
+
+fn parse_file(input: &str) -> OutputIResult<'_> {
     context(
-        "Output::StdOut",
+        "Output::File",
         map(
-            (tag_no_case(":to"), space1, tag_no_case("out"), space1), // 命令
-            |_| Output::new_std_out(),
+            terminated(
+                preceded(
+                    tag_no_case("file"), // 命令
+                    preceded(space1, general_file_info(":to file", false)),
+                ),
+                space1, // 丢弃：结尾空格
+            ),
+            |(file, append, crlf, compress, level)| Output::new_file(file, append, crlf, compress, level),
         ),
     )
     .parse(input)
 }
 
-fn parse_to_file(input: &str) -> OutputIResult<'_> {
+fn parse_split(input: &str) -> OutputIResult<'_> {
     context(
-        "Output::File",
+        "Output::Split",
         map(
             terminated(
-                preceded(
-                    (tag_no_case(":to"), space1, tag_no_case("file")), // 命令
-                    preceded(space1, general_file_info(false)),
+                (
+                    preceded((tag_no_case("split"), space1), general_file_info(":to split", false)), // 命令和文件信息
+                    preceded(
+                        (space1, tag_no_case("by"), space1),
+                        alt((
+                            map_res_failure(
+                                preceded((tag_no_case("reg"), space1), context("<regex>", arg)),
+                                SplitBy::new_reg,
+                            ), // 正则
+                            map(
+                                preceded(
+                                    (tag_no_case("batch"), space1),
+                                    context("<n>", verify(usize, |batch: &usize| *batch > 0)),
+                                ),
+                                SplitBy::Batch,
+                            ), // 批次大小
+                        )),
+                    ),
                 ),
                 space1, // 丢弃：结尾空格
             ),
-            |(file, append_opt, postfix_opt): (String, Option<_>, Option<&str>)| {
-                Output::new_file(file, append_opt.is_some(), postfix_opt.map(|s| s.eq_ignore_ascii_case("crlf")))
+            |((template, append, crlf, compress, level), by)| {
+                Output::new_split(template, by, append, crlf, compress, level)
             },
         ),
     )
     .parse(input)
 }
 
+fn parse_cmd(input: &str) -> OutputIResult<'_> {
+    context(
+        "Output::Cmd",
+        map_res_failure(
+            terminated(
+                cmd_arg1("cmd", "<program>"), // 命令、程序和参数
+                space1,                       // 丢弃：结尾空格
+            ),
+            |args| parse_to_cmd_arg(args, "<program>"),
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_format(input: &str) -> OutputIResult<'_> {
+    context("Output::Format", map(terminated(out_format, space1), Output::new_format)).parse(input)
+}
+
 #[cfg(windows)]
-fn parse_to_clip(input: &str) -> OutputIResult<'_> {
+fn parse_clip(input: &str) -> OutputIResult<'_> {
     context(
         "Output::Clip",
         map(
             preceded(
-                (tag_no_case(":to"), space1, tag_no_case("clip")), // 固定`:to clip`
+                tag_no_case("clip"), // 固定`clip`
                 terminated(
                     nom::combinator::opt(preceded(space1, alt((tag_no_case("lf"), tag_no_case("crlf"))))), // 换行符
                     space1,                                                                                // 结尾空格
                 ),
-            ), // 丢弃：`to clip `
+            ), // 丢弃：`clip `
             |postfix_opt: Option<&str>| Output::new_clip(postfix_opt.map(|s| s.eq_ignore_ascii_case("crlf"))),
         ),
     )
@@ -77,38 +132,151 @@ fn parse_to_clip(input: &str) -> OutputIResult<'_> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cmd::CmdArg;
+    use crate::compress::Compress;
+    use crate::out_format::OutFormat;
 
     #[test]
-    fn test_parse_to_file() {
-        assert_eq!(parse_to_file(":to file out.txt "), Ok(("", Output::new_file("out.txt".to_string(), false, None))));
+    fn test_parse_out() {
+        assert_eq!(parse_out(""), Ok(("", Output::new_std_out())));
+        assert_eq!(parse_out(":to out "), Ok(("", Output::new_std_out())));
         assert_eq!(
-            parse_to_file(":to file out.txt append "),
-            Ok(("", Output::new_file("out.txt".to_string(), true, None)))
+            parse_out(":to file out.txt crlf "),
+            Ok(("", Output::new_file("out.txt".to_string(), false, Some(true), None, None)))
         );
         assert_eq!(
-            parse_to_file(":to file out.txt append crlf "),
-            Ok(("", Output::new_file("out.txt".to_string(), true, Some(true))))
+            parse_out(":to out file a.txt file b.txt append crlf cmd sort -u "),
+            Ok((
+                "",
+                Output::new_tee(vec![
+                    Output::new_std_out(),
+                    Output::new_file("a.txt".to_string(), false, None, None, None),
+                    Output::new_file("b.txt".to_string(), true, Some(true), None, None),
+                    Output::new_cmd(CmdArg::new(vec!["sort".to_string(), "-u".to_string()]), None),
+                ])
+            ))
         );
+        assert_eq!(parse_out(":to other "), Ok((":to other ", Output::new_std_out())));
+    }
+
+    #[test]
+    fn test_parse_file() {
         assert_eq!(
-            parse_to_file(":to file out.txt crlf "),
-            Ok(("", Output::new_file("out.txt".to_string(), false, Some(true))))
+            parse_file("file out.txt "),
+            Ok(("", Output::new_file("out.txt".to_string(), false, None, None, None)))
         );
         assert_eq!(
-            parse_to_file(r#":to file "out .txt" "#),
-            Ok(("", Output::new_file("out .txt".to_string(), false, None)))
+            parse_file("file out.txt append "),
+            Ok(("", Output::new_file("out.txt".to_string(), true, None, None, None)))
+        );
+        assert_eq!(
+            parse_file("file out.txt append crlf "),
+            Ok(("", Output::new_file("out.txt".to_string(), true, Some(true), None, None)))
+        );
+        assert_eq!(
+            parse_file("file out.txt crlf "),
+            Ok(("", Output::new_file("out.txt".to_string(), false, Some(true), None, None)))
+        );
+        assert_eq!(
+            parse_file(r#"file "out .txt" "#),
+            Ok(("", Output::new_file("out .txt".to_string(), false, None, None, None)))
+        );
+        assert!(parse_file("file").is_err());
+        assert!(parse_file("file ").is_err());
+        assert!(parse_file("file [").is_err());
+        assert_eq!(
+            parse_file("file out.txt append ZSTD 19 "),
+            Ok(("", Output::new_file("out.txt".to_string(), true, None, Some(Compress::Zstd), Some(19))))
+        );
+        assert_eq!(
+            parse_file("file out.gz plain "),
+            Ok(("", Output::new_file("out.gz".to_string(), false, None, Some(Compress::Plain), None)))
+        );
+        assert!(parse_file("file out.txt zstd 0 ").is_err());
+        assert!(parse_file("file out.txt plain 1 ").is_err());
+        assert_eq!(
+            parse_file("file out.gz 9 "),
+            Ok(("", Output::new_file("out.gz".to_string(), false, None, None, Some(9))))
+        );
+        assert!(parse_file("file out.gz 10 ").is_err());
+        assert!(parse_file("file out.txt 9 ").is_err());
+    }
+
+    #[test]
+    fn test_parse_split() {
+        assert_eq!(
+            parse_split(r"split 'out/{key}.txt' by reg '^(\w+)' "),
+            Ok((
+                "",
+                Output::new_split(
+                    "out/{key}.txt".to_string(),
+                    SplitBy::new_reg(r"^(\w+)".to_string()).unwrap(),
+                    false,
+                    None,
+                    None,
+                    None
+                )
+            ))
+        );
+        assert_eq!(
+            parse_split("split part-{key:03}.txt.zst append zstd 3 by batch 1000 "),
+            Ok((
+                "",
+                Output::new_split(
+                    "part-{key:03}.txt.zst".to_string(),
+                    SplitBy::Batch(1000),
+                    true,
+                    None,
+                    Some(Compress::Zstd),
+                    Some(3)
+                )
+            ))
+        );
+        assert!(parse_split("split {key}.txt ").is_err());
+        assert!(parse_split("split {key}.txt by batch 0 ").is_err());
+        assert!(parse_split("split {key}.txt by reg ( ").is_err());
+    }
+
+    #[test]
+    fn test_parse_cmd() {
+        assert_eq!(
+            parse_cmd("cmd sort -u "),
+            Ok(("", Output::new_cmd(CmdArg::new(vec!["sort".to_string(), "-u".to_string()]), None)))
+        );
+        assert_eq!(
+            parse_cmd(r#"cmd lf sh -c "cat > out.txt" "#),
+            Ok((
+                "",
+                Output::new_cmd(
+                    CmdArg::new(vec!["sh".to_string(), "-c".to_string(), "cat > out.txt".to_string()]),
+                    Some(false)
+                )
+            ))
+        );
+        assert!(parse_cmd("cmd ").is_err());
+        assert!(parse_cmd("cmd crlf ").is_err());
+        assert!(parse_cmd("cmdx sort ").is_err());
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(parse_format("json "), Ok(("", Output::new_format(OutFormat::Json))));
+        assert_eq!(parse_format("JSONL "), Ok(("", Output::new_format(OutFormat::Jsonl))));
+        assert_eq!(
+            parse_format(r#"csv 'name,"a,b"' "#),
+            Ok(("", Output::new_format(OutFormat::Csv { header: vec!["name".to_string(), "a,b".to_string()] })))
         );
-        assert!(parse_to_file(":to").is_err());
-        assert!(parse_to_file(":to file ").is_err());
-        assert!(parse_to_file(":to file [").is_err());
+        assert!(parse_format("jsonx ").is_err());
+        assert!(parse_format("csv ").is_err());
     }
 
     #[test]
     #[cfg(windows)]
-    fn test_parse_to_clip() {
-        assert_eq!(parse_to_clip(":to clip "), Ok(("", Output::new_clip(None))));
-        assert_eq!(parse_to_clip(":to  clip  "), Ok(("", Output::new_clip(None))));
-        assert_eq!(parse_to_clip(":to clip lf "), Ok(("", Output::new_clip(Some(false)))));
-        assert_eq!(parse_to_clip(":to clip crlf "), Ok(("", Output::new_clip(Some(true)))));
-        assert!(parse_to_clip(":to ").is_err());
+    fn test_parse_clip() {
+        assert_eq!(parse_clip("clip "), Ok(("", Output::new_clip(None))));
+        assert_eq!(parse_clip("clip  "), Ok(("", Output::new_clip(None))));
+        assert_eq!(parse_clip("clip lf "), Ok(("", Output::new_clip(Some(false)))));
+        assert_eq!(parse_clip("clip crlf "), Ok(("", Output::new_clip(Some(true)))));
+        assert!(parse_clip("").is_err());
     }
 }
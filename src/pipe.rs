@@ -20,6 +20,13 @@ impl Pipe {
         Pipe { iter: Box::new(self.filter(f)) }
     }
 
+    pub(crate) fn op_flat_map<I>(self, f: impl FnMut(String) -> I + 'static) -> Pipe
+    where
+        I: IntoIterator<Item = String, IntoIter: 'static> + 'static,
+    {
+        Pipe { iter: Box::new(self.flat_map(f)) }
+    }
+
     pub(crate) fn op_inspect(self, f: impl FnMut(&String) + 'static) -> Pipe {
         Pipe { iter: Box::new(self.inspect(f)) }
     }
@@ -0,0 +1,43 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+thread_local! {
+    /// 当前数据的来源：文件名和行号（从1开始）。
+    static CURRENT: RefCell<Option<(Rc<String>, usize)>> = const { RefCell::new(None) };
+}
+
+/// 数据输入在产生每个数据前记录其来源。
+/// 由于流水线逐个拉取数据，流式操作处理数据时可以获取到此数据的来源；
+/// 输入结束后来源被清空，因此需要读取全部输入的操作（例如排序）之后无法获取来源。
+pub(crate) fn set_source(file: &Rc<String>, line_no: usize) {
+    CURRENT.with_borrow_mut(|current| match current {
+        Some((current_file, current_line_no)) if Rc::ptr_eq(current_file, file) => *current_line_no = line_no,
+        _ => *current = Some((file.clone(), line_no)),
+    });
+}
+
+pub(crate) fn clear_source() {
+    CURRENT.with_borrow_mut(|current| *current = None);
+}
+
+pub(crate) fn current_source() -> Option<(Rc<String>, usize)> {
+    CURRENT.with_borrow(|current| current.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source() {
+        let file = Rc::new("a.log".to_string());
+        set_source(&file, 1);
+        set_source(&file, 2);
+        assert_eq!(current_source(), Some((file.clone(), 2)));
+        let other = Rc::new("b.log".to_string());
+        set_source(&other, 1);
+        assert_eq!(current_source(), Some((other, 1)));
+        clear_source();
+        assert_eq!(current_source(), None);
+    }
+}